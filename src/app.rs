@@ -1,42 +1,714 @@
+use std::{
+    env,
+    path::{Component, Path, PathBuf},
+};
+
+use serde_json::{json, Map};
+
 use crate::{
-    state::TerminalState,
-    util::{AvailableOption, EditMode, ItemOption, MenuType},
+    check::{self, Problem},
+    export,
+    generator::{
+        self,
+        advancement::{self, AdvancementSpec, Rewards},
+        biome::{self, BiomeColors, BiomeSpec, Spawn},
+        block::{BlockSpec, BlockTemplate},
+        crop::CropSpec,
+        dimension::{DimensionSpec, DimensionType, Generator},
+        food::{FoodEffect, FoodProperties},
+        function::{FunctionSpec, RUN_ON, TEMPLATES},
+        item::ItemSpec,
+        item_model,
+        model::{self, Override},
+        ore::OreSpec,
+        pack::{PackKind, PackSpec},
+        particle::ParticleSpec,
+        sound::{SoundFile, SoundSpec},
+        worldgen::{self, FeatureType, Field, FieldKind, FEATURE_TYPES},
+    },
+    journal,
+    loader::Loader,
+    project::Project,
+    refactor::{self, rename::RenameSpec},
+    settings::Settings,
+    state::{ScreenOptions, TerminalState},
+    util::{AvailableOption, EditMode, ItemOption, MenuType, StatefulList},
+    version::GameVersion,
 };
 
 /// This struct holds the current state of the app.
 pub struct App<'a> {
     pub namespace: String,
+    /// The namespace before it was last edited, which Enter migrates the
+    /// project's files from.
+    pub previous_namespace: String,
+    pub version: String,
+    pub loader: Loader,
+    pub root: PathBuf,
     pub mode: EditMode,
     pub state: TerminalState<'a>,
     pub menu: MenuType,
+    /// Outcome of the last action, shown at the bottom of the screen.
+    pub status: Option<String>,
+    /// The folder the tool was started in, holding the settings file.
+    project_root: PathBuf,
+    /// The settings as the project file last held them, to tell when they
+    /// change.
+    settings: Settings,
+    /// What applies where the project file sets nothing: the user-level
+    /// settings over the built-in defaults.
+    inherited: Settings,
+    /// The "Generate lang file" option of every screen as last seen, to tell
+    /// which one was toggled.
+    lang_toggles: Vec<bool>,
+}
+
+/// The parent the handheld default picks for new items.
+const HANDHELD: &str = "minecraft:item/handheld";
+
+/// Where `path` is relative to `root`, `.` when it's `root` itself.
+fn relative_output(root: &Path, path: &Path) -> String {
+    match path.strip_prefix(root) {
+        Ok(relative) if relative.as_os_str().is_empty() => String::from("."),
+        Ok(relative) => relative.to_string_lossy().replace('\\', "/"),
+        Err(_) => path.to_string_lossy().into_owned(),
+    }
+}
+
+/// The settings of a project nothing was set up for.
+fn builtin_settings() -> Settings {
+    Settings {
+        namespace: Some(String::from("modid")),
+        version: Some(String::from("1.20.1")),
+        loader: Some(Loader::Forge),
+        output: Some(String::from(".")),
+        handheld: Some(false),
+        generate_lang: Some(true),
+    }
 }
 
 impl<'a> App<'a> {
+    /// Starts with the settings of the project in the current folder, over
+    /// the user-level ones.
     pub fn new() -> Self {
-        Self {
-            namespace: String::from("modid"),
+        let project_root = env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+        let (saved, user, error) = Settings::load(&project_root);
+        let inherited = user.or(builtin_settings());
+        let settings = saved.clone().or(inherited.clone());
+        let namespace = settings.namespace.clone().unwrap_or_default();
+        let mut app = Self {
+            previous_namespace: namespace.clone(),
+            namespace,
+            version: settings.version.clone().unwrap_or_default(),
+            loader: settings.loader.unwrap_or(Loader::Forge),
+            root: match settings.output.as_deref() {
+                None | Some(".") => project_root.clone(),
+                Some(output) => project_root.join(output),
+            },
             mode: EditMode::None,
             state: TerminalState::new(
                 vec![
                     AvailableOption::new("Create Item", "Generates JSON files for an item."),
                     AvailableOption::new("Create Block", "Generates JSON files for a block."),
-                ],
-                vec![
-                    ItemOption::new(
-                        "Handheld",
-                        "Whether your item inherits handheld properties ('generated' is default).",
+                    AvailableOption::new(
+                        "Create Crop",
+                        "Generates the growth stages, seeds and loot table of a crop.",
+                    ),
+                    AvailableOption::new(
+                        "Create Ore",
+                        "Generates an ore with its drops, recipes and world generation.",
+                    ),
+                    AvailableOption::new(
+                        "Create Feature",
+                        "Generates a configured and placed feature, like a tree or a patch.",
+                    ),
+                    AvailableOption::new(
+                        "Create Biome",
+                        "Generates a biome with its climate, colors, spawns and features.",
+                    ),
+                    AvailableOption::new(
+                        "Create Dimension",
+                        "Generates a dimension and its dimension type.",
+                    ),
+                    AvailableOption::new(
+                        "Create Advancement",
+                        "Generates an advancement and shows the tree of your advancements.",
+                    ),
+                    AvailableOption::new(
+                        "Create Sound Event",
+                        "Adds a sound event to sounds.json with its subtitle.",
+                    ),
+                    AvailableOption::new(
+                        "Create Particle",
+                        "Generates a particle listing the frames found in textures/particle.",
+                    ),
+                    AvailableOption::new(
+                        "New Pack",
+                        "Creates a data or resource pack that the generators then write into.",
+                    ),
+                    AvailableOption::new(
+                        "Create Function",
+                        "Creates a function from a template and runs it on load or every tick.",
+                    ),
+                    AvailableOption::new(
+                        "Lint Functions",
+                        "Lists the mistakes found in the commands of every function.",
                     ),
-                    ItemOption::active(
-                        "Generate lang file",
-                        "A lang json file will be generated with the translation for your item.",
+                    AvailableOption::new(
+                        "Check References",
+                        "Lists the missing textures, models, items and names of your namespace.",
+                    ),
+                    AvailableOption::new(
+                        "Find Unused Assets",
+                        "Lists the textures, models and names nothing uses, to delete them.",
+                    ),
+                    AvailableOption::new(
+                        "Rename Id",
+                        "Renames an item or block in every model, recipe, tag and lang file.",
                     ),
                 ],
+                ScreenOptions {
+                    item: vec![
+                        ItemOption::choice(
+                            "Model parent",
+                            "The model your item inherits from, including the ones in your project.",
+                            model::VANILLA_PARENTS.to_vec(),
+                        ),
+                        ItemOption::text(
+                            "Texture layers",
+                            "Comma separated textures for layer0, layer1... (defaults to item/<id>).",
+                            "",
+                        ),
+                        ItemOption::text(
+                            "Third person transform",
+                            "Written as 'rotation x y z; translation x y z; scale x y z'.",
+                            "",
+                        ),
+                        ItemOption::text(
+                            "GUI transform",
+                            "Written as 'rotation x y z; translation x y z; scale x y z'.",
+                            "",
+                        ),
+                        ItemOption::active(
+                            "Generate lang file",
+                            "A lang json file will be generated with the translation for your item.",
+                        ),
+                        ItemOption::int(
+                            "Stack size",
+                            "How many of your item fit in a single slot.",
+                            64,
+                            1,
+                            99,
+                        ),
+                        ItemOption::choice(
+                            "Rarity",
+                            "The color of your item's name.",
+                            vec!["common", "uncommon", "rare", "epic"],
+                        ),
+                        ItemOption::int(
+                            "Durability",
+                            "How many uses your item has before breaking, 0 if it can't break.",
+                            0,
+                            0,
+                            100000,
+                        ),
+                        ItemOption::new("Food", "Whether your item can be eaten."),
+                        ItemOption::int("Nutrition", "Hunger points your food restores.", 4, 0, 20),
+                        ItemOption::float(
+                            "Saturation",
+                            "Saturation relative to the nutrition (the apple uses 0.3).",
+                            0.3,
+                            0.0,
+                            5.0,
+                        ),
+                        ItemOption::new(
+                            "Always edible",
+                            "Whether your food can be eaten even when the player is not hungry.",
+                        ),
+                        ItemOption::new(
+                            "Fast to eat",
+                            "Whether your food is eaten in half the time, like dried kelp.",
+                        ),
+                        ItemOption::text(
+                            "Effects",
+                            "Written as 'id duration [amplifier] [probability]; ...'.",
+                            "",
+                        ),
+                    ],
+                    block: vec![
+                        ItemOption::choice(
+                            "Model template",
+                            "The vanilla model your block is built from.",
+                            BlockTemplate::NAMES.to_vec(),
+                        ),
+                        ItemOption::text(
+                            "Textures",
+                            "Written as 'slot=texture, ...' (defaults to block/<id>_<slot>).",
+                            "",
+                        ),
+                        ItemOption::choice(
+                            "Render type",
+                            "How transparent pixels are drawn (cross models default to cutout).",
+                            vec!["solid", "cutout", "cutout_mipped", "translucent"],
+                        ),
+                        ItemOption::active(
+                            "Generate lang file",
+                            "A lang json file will be generated with the translation for your block.",
+                        ),
+                        ItemOption::active(
+                            "Generate block item",
+                            "An item model will be generated so your block shows in the inventory.",
+                        ),
+                    ],
+                    crop: vec![
+                        ItemOption::int(
+                            "Max age",
+                            "The last growth stage, when your crop can be harvested (wheat uses 7).",
+                            7,
+                            1,
+                            15,
+                        ),
+                        ItemOption::text(
+                            "Seed item",
+                            "The item planting your crop (defaults to <id>_seeds).",
+                            "",
+                        ),
+                        ItemOption::text(
+                            "Produce item",
+                            "The item dropped by a grown crop (defaults to <id>).",
+                            "",
+                        ),
+                        ItemOption::active(
+                            "Generate lang file",
+                            "A lang json file will be generated with the translations for your crop.",
+                        ),
+                    ],
+                    ore: vec![
+                        ItemOption::text(
+                            "Smelts into",
+                            "The item your ore and raw item smelt into (defaults to <material>_ingot).",
+                            "",
+                        ),
+                        ItemOption::float(
+                            "Experience",
+                            "Experience given for each smelted item (iron gives 0.7).",
+                            0.7,
+                            0.0,
+                            100.0,
+                        ),
+                        ItemOption::int(
+                            "Vein size",
+                            "How many blocks a vein has at most.",
+                            9,
+                            1,
+                            64,
+                        ),
+                        ItemOption::int(
+                            "Veins per chunk",
+                            "How many veins are tried per chunk.",
+                            10,
+                            1,
+                            256,
+                        ),
+                        ItemOption::int(
+                            "Min height",
+                            "The lowest Y your ore generates at.",
+                            -64,
+                            -2032,
+                            2031,
+                        ),
+                        ItemOption::int(
+                            "Max height",
+                            "The highest Y your ore generates at.",
+                            64,
+                            -2032,
+                            2031,
+                        ),
+                        ItemOption::choice(
+                            "Height distribution",
+                            "Trapezoid makes veins most common halfway between the heights.",
+                            vec!["uniform", "trapezoid"],
+                        ),
+                        ItemOption::choice(
+                            "Tool tier",
+                            "The pickaxe needed to mine your ore.",
+                            vec!["stone", "iron", "diamond"],
+                        ),
+                        ItemOption::active(
+                            "Recipe unlocks",
+                            "The recipes will be unlocked in the recipe book by their ingredients.",
+                        ),
+                        ItemOption::active(
+                            "Generate lang file",
+                            "A lang json file will be generated with the translations for your ore.",
+                        ),
+                    ],
+                    feature: vec![
+                        ItemOption::choice(
+                            "Feature type",
+                            "The vanilla feature your feature configures.",
+                            FEATURE_TYPES.iter().map(|kind| kind.name).collect(),
+                        ),
+                    ],
+                    biome: vec![
+                        ItemOption::float(
+                            "Temperature",
+                            "Decides snow and the default plant colors (plains use 0.8).",
+                            0.8,
+                            -2.0,
+                            2.0,
+                        ),
+                        ItemOption::float(
+                            "Downfall",
+                            "How humid the biome is, tinting the default plant colors.",
+                            0.4,
+                            0.0,
+                            1.0,
+                        ),
+                        ItemOption::choice(
+                            "Precipitation",
+                            "What falls when it rains (snow follows the temperature from 1.19.4).",
+                            vec!["rain", "snow", "none"],
+                        ),
+                        ItemOption::color("Sky color", "The color of the sky.", "78a7ff"),
+                        ItemOption::color("Fog color", "The color of the fog.", "c0d8ff"),
+                        ItemOption::color("Water color", "The color of water.", "3f76e4"),
+                        ItemOption::color(
+                            "Water fog color",
+                            "The color of the fog under water.",
+                            "050533",
+                        ),
+                        ItemOption::color(
+                            "Grass color",
+                            "The color of grass (empty follows the climate).",
+                            "",
+                        ),
+                        ItemOption::color(
+                            "Foliage color",
+                            "The color of leaves and vines (empty follows the climate).",
+                            "",
+                        ),
+                        ItemOption::active(
+                            "Caves",
+                            "Whether vanilla caves and canyons are carved.",
+                        ),
+                        ItemOption::text(
+                            "Tags",
+                            "Comma separated biome tags to add your biome to.",
+                            "is_overworld",
+                        ),
+                        ItemOption::active(
+                            "Generate lang file",
+                            "A lang json file will be generated with the translation for your biome.",
+                        ),
+                    ],
+                    dimension: vec![
+                        ItemOption::choice(
+                            "Generator",
+                            "Vanilla terrain and biomes, vanilla terrain with a single biome, or flat.",
+                            vec!["noise", "fixed_biome", "flat"],
+                        ),
+                        ItemOption::choice(
+                            "Noise settings",
+                            "The vanilla terrain shape of noise and fixed biome dimensions.",
+                            vec![
+                                "overworld",
+                                "nether",
+                                "end",
+                                "amplified",
+                                "large_biomes",
+                                "caves",
+                                "floating_islands",
+                            ],
+                        ),
+                        ItemOption::text(
+                            "Biome",
+                            "The biome of fixed biome and flat dimensions.",
+                            "minecraft:plains",
+                        ),
+                        ItemOption::text(
+                            "Flat layers",
+                            "Written as 'block height, ...' from the bottom up.",
+                            "bedrock 1, dirt 2, grass_block 1",
+                        ),
+                        ItemOption::int(
+                            "Min Y",
+                            "The lowest block, a multiple of 16.",
+                            -64,
+                            -2032,
+                            2016,
+                        ),
+                        ItemOption::int(
+                            "Height",
+                            "How many blocks high the world is, a multiple of 16.",
+                            384,
+                            16,
+                            4064,
+                        ),
+                        ItemOption::int(
+                            "Logical height",
+                            "How high portals and chorus fruit can take players.",
+                            384,
+                            0,
+                            4064,
+                        ),
+                        ItemOption::float(
+                            "Ambient light",
+                            "How bright the dimension is without any light (the nether uses 0.1).",
+                            0.0,
+                            0.0,
+                            1.0,
+                        ),
+                        ItemOption::text(
+                            "Fixed time",
+                            "A time of day that never changes, empty for a day cycle.",
+                            "",
+                        ),
+                        ItemOption::choice(
+                            "Effects",
+                            "The vanilla sky and fog the dimension looks like.",
+                            vec!["overworld", "the_nether", "the_end"],
+                        ),
+                        ItemOption::active("Has skylight", "Whether the sky lights the world."),
+                        ItemOption::new("Has ceiling", "Whether there is a bedrock ceiling."),
+                        ItemOption::new(
+                            "Ultrawarm",
+                            "Whether water evaporates and lava spreads fast.",
+                        ),
+                        ItemOption::active(
+                            "Natural",
+                            "Whether compasses work and portals spawn piglins.",
+                        ),
+                        ItemOption::float(
+                            "Coordinate scale",
+                            "How far one block takes you in the overworld (the nether uses 8).",
+                            1.0,
+                            0.00001,
+                            30000000.0,
+                        ),
+                        ItemOption::active(
+                            "Bed works",
+                            "Whether beds can be slept in instead of exploding.",
+                        ),
+                        ItemOption::new(
+                            "Respawn anchor works",
+                            "Whether respawn anchors can be used instead of exploding.",
+                        ),
+                        ItemOption::new("Piglin safe", "Whether piglins don't turn into zombies."),
+                        ItemOption::active("Has raids", "Whether bad omen starts raids."),
+                        ItemOption::int(
+                            "Monster spawn light",
+                            "The highest light level monsters spawn at (1.19 and later).",
+                            0,
+                            0,
+                            15,
+                        ),
+                        ItemOption::text(
+                            "Infiniburn",
+                            "The block tag fire burns forever on.",
+                            "#minecraft:infiniburn_overworld",
+                        ),
+                    ],
+                    advancement: vec![
+                        ItemOption::choice(
+                            "Parent",
+                            "The advancement before yours, none for the root of a new tab.",
+                            vec!["none"],
+                        ),
+                        ItemOption::text(
+                            "Icon",
+                            "The item shown in the frame.",
+                            "minecraft:diamond",
+                        ),
+                        ItemOption::choice(
+                            "Frame",
+                            "The shape of the frame, goals and challenges stand out more.",
+                            vec!["task", "goal", "challenge"],
+                        ),
+                        ItemOption::text(
+                            "Background",
+                            "The tab background, only used by roots.",
+                            "minecraft:textures/gui/advancements/backgrounds/stone.png",
+                        ),
+                        ItemOption::active(
+                            "Show toast",
+                            "Whether a popup shows when it's completed.",
+                        ),
+                        ItemOption::active(
+                            "Announce to chat",
+                            "Whether completing it is announced in the chat.",
+                        ),
+                        ItemOption::new("Hidden", "Whether it stays hidden until it's completed."),
+                        ItemOption::new(
+                            "Any criterion",
+                            "Whether any criterion completes it instead of all of them.",
+                        ),
+                        ItemOption::int(
+                            "Experience",
+                            "Experience points given as a reward.",
+                            0,
+                            0,
+                            100000,
+                        ),
+                        ItemOption::text(
+                            "Loot",
+                            "Comma separated loot tables given as a reward.",
+                            "",
+                        ),
+                        ItemOption::text(
+                            "Recipes",
+                            "Comma separated recipes unlocked as a reward.",
+                            "",
+                        ),
+                        ItemOption::text("Function", "A function run as a reward.", ""),
+                        ItemOption::active(
+                            "Generate lang file",
+                            "A lang json file will be generated with the title and description.",
+                        ),
+                    ],
+                    sound: vec![
+                        ItemOption::new(
+                            "Replace",
+                            "Whether your sounds replace the ones of lower resource packs.",
+                        ),
+                        ItemOption::active(
+                            "Registration snippet",
+                            "A Java snippet registering the sound event will be generated.",
+                        ),
+                        ItemOption::active(
+                            "Generate lang file",
+                            "A lang json file will be generated with the subtitle.",
+                        ),
+                    ],
+                    particle: vec![
+                        ItemOption::new(
+                            "Always show",
+                            "Whether it shows even with particles set to minimal.",
+                        ),
+                        ItemOption::active(
+                            "Registration snippet",
+                            "A Java snippet registering the particle type will be generated.",
+                        ),
+                    ],
+                    pack: vec![
+                        ItemOption::choice(
+                            "Pack type",
+                            "Data packs hold data/, resource packs hold assets/.",
+                            vec!["data pack", "resource pack"],
+                        ),
+                        ItemOption::text(
+                            "Supported up to",
+                            "The newest version the pack works on, empty for the target only.",
+                            "",
+                        ),
+                        ItemOption::active(
+                            "Placeholder icon",
+                            "A grey pack.png will be created to replace with your own.",
+                        ),
+                    ],
+                    function: vec![
+                        ItemOption::choice(
+                            "Template",
+                            "The commands the function starts with.",
+                            TEMPLATES.to_vec(),
+                        ),
+                        ItemOption::text(
+                            "Objective",
+                            "The scoreboard objective the template uses, empty for the namespace.",
+                            "",
+                        ),
+                        ItemOption::choice(
+                            "Run on",
+                            "The function tag that makes the game run it.",
+                            RUN_ON.to_vec(),
+                        ),
+                    ],
+                    rename: vec![
+                        ItemOption::new(
+                            "Rename textures",
+                            "Renames the block and item textures named after the id too.",
+                        ),
+                    ],
+                },
             ),
             menu: MenuType::MainMenu,
+            status: error,
+            project_root,
+            settings: saved,
+            inherited,
+            lang_toggles: Vec::new(),
+        };
+        if let Some(lang) = settings.generate_lang {
+            for options in app.state.all_options() {
+                options.set_active("Generate lang file", lang);
+            }
+        }
+        if settings.handheld == Some(true) {
+            app.state
+                .item_options()
+                .set_choice("Model parent", HANDHELD);
+        }
+        app.lang_toggles = app.lang_toggles();
+        app
+    }
+
+    fn lang_toggles(&mut self) -> Vec<bool> {
+        self.state
+            .all_options()
+            .iter()
+            .filter_map(|options| options.toggle("Generate lang file"))
+            .collect()
+    }
+
+    /// What the project file should hold for the settings on screen. The
+    /// lang default follows the last screen it was toggled on, leaving the
+    /// other screens as they are until the next launch.
+    fn current_settings(&mut self) -> Settings {
+        let toggles = self.lang_toggles();
+        let toggled = toggles
+            .iter()
+            .zip(&self.lang_toggles)
+            .find(|(now, before)| now != before)
+            .map(|(now, _)| *now);
+        let generate_lang = toggled
+            .or(self.settings.generate_lang)
+            .or(self.inherited.generate_lang);
+        self.lang_toggles = toggles;
+        let on_screen = Settings {
+            namespace: Some(self.namespace.clone()),
+            version: Some(self.version.clone()),
+            loader: Some(self.loader),
+            output: Some(relative_output(&self.project_root, &self.root)),
+            handheld: Some(self.state.item_options().choice("Model parent") == HANDHELD),
+            generate_lang,
+        };
+        on_screen.over(&self.settings, &self.inherited)
+    }
+
+    /// Saves the settings to the project's settings file when they changed.
+    /// Only what the project sets itself is written, so the user-level
+    /// settings keep applying to the rest. Fields being typed are saved once
+    /// they are left, and a new namespace once its migration is applied.
+    pub fn save_settings(&mut self) {
+        let pending = matches!(
+            self.mode,
+            EditMode::Namespace | EditMode::Version | EditMode::ChangePreview
+        );
+        if pending {
+            return;
+        }
+        let settings = self.current_settings();
+        if settings == self.settings {
+            return;
         }
+        if let Err(err) = settings.save(&self.project_root) {
+            self.status = Some(format!("The settings couldn't be saved: {}", err));
+        }
+        self.settings = settings;
     }
     pub fn navigate(&mut self, menu: MenuType) {
         self.mode = EditMode::None;
+        self.status = None;
         self.menu = menu;
     }
     pub fn current_menu(&self) -> &MenuType {
@@ -46,4 +718,1023 @@ impl<'a> App<'a> {
         self.mode = mode;
     }
     pub fn tick(&self) {}
+
+    /// The project files are generated into, for the current namespace and
+    /// target version.
+    pub fn project(&self) -> Result<Project, String> {
+        self.project_of(&self.namespace)
+    }
+
+    fn project_of(&self, namespace: &str) -> Result<Project, String> {
+        if namespace.is_empty() {
+            return Err(String::from("The namespace can't be empty."));
+        }
+        match GameVersion::parse(&self.version) {
+            Some(version) => Ok(Project::new(&self.root, namespace, version, self.loader)),
+            None => Err(format!("'{}' is not a valid version.", self.version)),
+        }
+    }
+
+    /// Undoes the last generation, even one made before the tool was
+    /// restarted.
+    pub fn undo(&mut self) {
+        self.status = Some(match journal::undo(&self.root) {
+            Ok(Some(count)) => format!("Undid the last generation, restoring {} file(s).", count),
+            Ok(None) => String::from("There is nothing to undo."),
+            Err(err) => err.to_string(),
+        });
+    }
+
+    /// Brings back the last generation undone.
+    pub fn redo(&mut self) {
+        self.status = Some(match journal::redo(&self.root) {
+            Ok(Some(count)) => format!("Redid the last generation, changing {} file(s).", count),
+            Ok(None) => String::from("There is nothing to redo."),
+            Err(err) => err.to_string(),
+        });
+    }
+
+    /// Starts editing the namespace, remembering the current one to migrate
+    /// from.
+    pub fn edit_namespace(&mut self) {
+        self.previous_namespace = self.namespace.clone();
+        self.set_mode(EditMode::Namespace);
+    }
+
+    /// Plans the move of the previous namespace's files to the one typed
+    /// and lists every change for review.
+    pub fn preview_migration(&mut self) {
+        self.set_mode(EditMode::None);
+        if self.namespace == self.previous_namespace {
+            return;
+        }
+        let result = self.project().and_then(|_| {
+            let project = self.project_of(&self.previous_namespace)?;
+            refactor::namespace::plan(&project, &self.namespace)
+        });
+        match result {
+            Ok(changes) if changes.is_empty() => {
+                self.status = Some(format!(
+                    "There was nothing in '{}' to move, the namespace is now '{}'.",
+                    self.previous_namespace, self.namespace
+                ));
+            }
+            Ok(changes) => {
+                self.status = Some(format!("{} file(s) will change.", changes.len()));
+                *self.state.changes() = StatefulList::of(changes);
+                self.state.changes().next();
+                self.set_mode(EditMode::ChangePreview);
+            }
+            Err(err) => {
+                self.namespace = self.previous_namespace.clone();
+                self.status = Some(format!("{} The namespace stays '{}'.", err, self.namespace));
+            }
+        }
+    }
+
+    /// Drops the previewed migration, or the namespace being typed, going
+    /// back to the namespace the files are still in.
+    pub fn decline_migration(&mut self) {
+        self.set_mode(EditMode::None);
+        *self.state.changes() = StatefulList::of(vec![]);
+        if self.namespace != self.previous_namespace {
+            self.namespace = self.previous_namespace.clone();
+            self.status = Some(format!(
+                "Declined the migration, the namespace stays '{}'.",
+                self.namespace
+            ));
+        }
+    }
+
+    /// Makes the previewed migration's changes and lists the code still
+    /// using the previous namespace.
+    pub fn apply_migration(&mut self) {
+        let changes = std::mem::replace(self.state.changes(), StatefulList::of(vec![]));
+        let result = self.project().and_then(|project| {
+            refactor::apply(&project, changes.elements()).map_err(|err| err.to_string())
+        });
+        match result {
+            Ok(count) => {
+                self.navigate(MenuType::MigrationMenu);
+                self.find_code_references();
+                let left = self.state.problems().elements().len();
+                self.status = Some(format!(
+                    "Moved {} file(s) to '{}'. {} line(s) of code still use '{}'.",
+                    count, self.namespace, left, self.previous_namespace
+                ));
+            }
+            // Part of the files may have moved already, which undo takes back
+            Err(err) => {
+                self.set_mode(EditMode::None);
+                self.namespace = self.previous_namespace.clone();
+                self.status = Some(format!(
+                    "{} The namespace stays '{}', press u to undo the files already moved.",
+                    err, self.namespace
+                ));
+            }
+        }
+    }
+
+    /// Lists the lines of code still using the namespace migrated from.
+    pub fn find_code_references(&mut self) {
+        let problems = refactor::namespace::code_references(&self.root, &self.previous_namespace);
+        self.status = Some(problems_status(&problems));
+        *self.state.problems() = StatefulList::of(problems);
+        self.state.problems().next();
+    }
+
+    fn item_spec(&mut self, project: &Project) -> Result<ItemSpec, String> {
+        let fields = self.state.item_text_fields();
+        let id = fields.value("Identifier").to_string();
+        if id.is_empty() {
+            return Err(String::from("The item identifier can't be empty."));
+        }
+        let name = match fields.value("Display Name") {
+            "" => generator::display_name(&id),
+            name => name.to_string(),
+        };
+        let mut overrides = Vec::new();
+        for row in self.state.item_overrides().elements() {
+            if row.trim().is_empty() {
+                continue;
+            }
+            let entry = Override::parse(project, row)?;
+            if entry.model() == id {
+                return Err(String::from(
+                    "An override can't point to the item model itself.",
+                ));
+            }
+            overrides.push(entry);
+        }
+        if !overrides.is_empty() && project.version().at_least(21, 4) {
+            return Err(String::from(
+                "Model overrides were replaced in 1.21.4, use the item model tree instead.",
+            ));
+        }
+        let definition = item_model::parse_tree(project, self.state.item_model_tree().elements())?;
+        let options = self.state.item_options();
+        let stack_size = options.int("Stack size")? as u32;
+        let durability = options.int("Durability")? as u32;
+        if durability > 0 && stack_size > 1 {
+            return Err(String::from(
+                "Items with durability must have a stack size of 1.",
+            ));
+        }
+        // Stacks above 64 came with item components
+        if stack_size > 64 && !project.version().at_least(20, 5) {
+            return Err(String::from("Stack sizes above 64 need 1.20.5 or later."));
+        }
+        let food = if options.is_active("Food") {
+            Some(FoodProperties {
+                nutrition: options.int("Nutrition")? as u32,
+                saturation_modifier: options.float("Saturation")?,
+                always_edible: options.is_active("Always edible"),
+                fast: options.is_active("Fast to eat"),
+                effects: FoodEffect::parse_list(options.text("Effects"))?,
+            })
+        } else {
+            None
+        };
+        let parent = options.choice("Model parent").to_string();
+        if parent == project.resource(&format!("item/{}", id)) {
+            return Err(String::from("An item model can't be its own parent."));
+        }
+        let mut layers = model::layers(project, options.text("Texture layers"));
+        // Items shown as their block model don't need a texture of their own
+        let block_model = parent.contains(":block/");
+        if layers.is_empty() && !block_model {
+            layers.insert(
+                "layer0".into(),
+                json!(project.resource(&format!("item/{}", id))),
+            );
+        }
+        let mut display = Map::new();
+        let transforms = [
+            ("thirdperson_righthand", "Third person transform"),
+            ("gui", "GUI transform"),
+        ];
+        for (context, name) in transforms {
+            if let Some(transform) = model::transform(options.text(name))? {
+                display.insert(context.into(), transform);
+            }
+        }
+        Ok(ItemSpec {
+            id,
+            name,
+            parent,
+            layers,
+            display,
+            overrides,
+            definition,
+            lang: options.is_active("Generate lang file"),
+            stack_size,
+            rarity: options.choice("Rarity").to_string(),
+            durability,
+            food,
+        })
+    }
+
+    fn block_spec(&mut self, project: &Project) -> Result<BlockSpec, String> {
+        let fields = self.state.block_text_fields();
+        let id = fields.value("Identifier").to_string();
+        if id.is_empty() {
+            return Err(String::from("The block identifier can't be empty."));
+        }
+        let name = match fields.value("Display Name") {
+            "" => generator::display_name(&id),
+            name => name.to_string(),
+        };
+        let options = self.state.block_options();
+        let template = BlockTemplate::parse(options.choice("Model template"))
+            .unwrap_or(BlockTemplate::CubeAll);
+        let textures = template.textures(project, &id, options.text("Textures"))?;
+        let render_type = match options.choice("Render type") {
+            "solid" if template == BlockTemplate::Cross => Some(String::from("cutout")),
+            "solid" => None,
+            render_type => Some(render_type.to_string()),
+        };
+        Ok(BlockSpec {
+            id,
+            name,
+            template,
+            textures,
+            render_type,
+            lang: options.is_active("Generate lang file"),
+            item: options.is_active("Generate block item"),
+        })
+    }
+
+    /// Generates the files of the block being edited and reports the outcome
+    /// in the status line.
+    pub fn generate_block(&mut self) {
+        let result = self.project().and_then(|project| {
+            let block = self.block_spec(&project)?;
+            generator::block::generate(&project, &block).map_err(|err| err.to_string())
+        });
+        self.status = Some(match result {
+            Ok(files) => format!("Generated {} file(s).", files.len()),
+            Err(err) => err,
+        });
+    }
+
+    fn crop_spec(&mut self) -> Result<CropSpec, String> {
+        let id = self
+            .state
+            .crop_text_fields()
+            .value("Identifier")
+            .to_string();
+        if id.is_empty() {
+            return Err(String::from("The crop identifier can't be empty."));
+        }
+        let options = self.state.crop_options();
+        let seed = match options.text("Seed item") {
+            "" => format!("{}_seeds", id),
+            seed => seed.to_lowercase(),
+        };
+        let produce = match options.text("Produce item") {
+            "" => id.clone(),
+            produce => produce.to_lowercase(),
+        };
+        Ok(CropSpec {
+            max_age: options.int("Max age")? as u32,
+            lang: options.is_active("Generate lang file"),
+            id,
+            seed,
+            produce,
+        })
+    }
+
+    /// Generates the files of the crop being edited and reports the outcome
+    /// in the status line.
+    pub fn generate_crop(&mut self) {
+        let result = self.project().and_then(|project| {
+            let crop = self.crop_spec()?;
+            generator::crop::generate(&project, &crop).map_err(|err| err.to_string())
+        });
+        self.status = Some(match result {
+            Ok(files) => format!("Generated {} file(s).", files.len()),
+            Err(err) => err,
+        });
+    }
+
+    fn ore_spec(&mut self) -> Result<OreSpec, String> {
+        let material = self.state.ore_text_fields().value("Material").to_string();
+        if material.is_empty() {
+            return Err(String::from("The ore material can't be empty."));
+        }
+        let options = self.state.ore_options();
+        let min_height = options.int("Min height")? as i32;
+        let max_height = options.int("Max height")? as i32;
+        if min_height > max_height {
+            return Err(String::from(
+                "The min height can't be above the max height.",
+            ));
+        }
+        let smelts_into = match options.text("Smelts into") {
+            "" => format!("{}_ingot", material),
+            item => item.to_lowercase(),
+        };
+        Ok(OreSpec {
+            smelts_into,
+            experience: options.float("Experience")?,
+            vein_size: options.int("Vein size")? as u32,
+            count: options.int("Veins per chunk")? as u32,
+            min_height,
+            max_height,
+            distribution: options.choice("Height distribution").to_string(),
+            tool: options.choice("Tool tier").to_string(),
+            lang: options.is_active("Generate lang file"),
+            unlocks: options.is_active("Recipe unlocks"),
+            material,
+        })
+    }
+
+    /// Generates the files of the ore being edited and reports the outcome
+    /// in the status line.
+    pub fn generate_ore(&mut self) {
+        let result = self.project().and_then(|project| {
+            let ore = self.ore_spec()?;
+            generator::ore::generate(&project, &ore).map_err(|err| err.to_string())
+        });
+        self.status = Some(match result {
+            Ok(files) => format!("Generated {} file(s).", files.len()),
+            Err(err) => err,
+        });
+    }
+
+    /// Swaps the field options for the ones of the selected feature type
+    /// when it changes.
+    pub fn refresh_feature_fields(&mut self) {
+        let options = self.state.feature_options();
+        let kind = match FeatureType::parse(options.choice("Feature type")) {
+            Some(kind) => kind,
+            None => return,
+        };
+        let current = options.elements()[1..].iter().map(|o| o.get_option());
+        if !current.eq(kind.fields.iter().map(|field| field.path)) {
+            options.replace_after(1, kind.fields.iter().map(field_option).collect());
+        }
+    }
+
+    /// Generates the configured and placed feature being edited and reports
+    /// the outcome in the status line.
+    pub fn generate_feature(&mut self) {
+        let result = self.project().and_then(|project| {
+            let id = self
+                .state
+                .feature_text_fields()
+                .value("Identifier")
+                .to_string();
+            if id.is_empty() {
+                return Err(String::from("The feature identifier can't be empty."));
+            }
+            let placement = worldgen::placement(self.state.feature_placement().elements())?;
+            let options = self.state.feature_options();
+            let kind = FeatureType::parse(options.choice("Feature type"))
+                .ok_or_else(|| String::from("Pick a feature type."))?;
+            let mut values = Vec::new();
+            for field in kind.fields {
+                values.push((field.path, options.raw(field.path)?));
+            }
+            let configured = kind.configured(&project, &values)?;
+            worldgen::generate(&project, &id, &configured, placement).map_err(|err| err.to_string())
+        });
+        self.status = Some(match result {
+            Ok(files) => format!("Generated {} file(s).", files.len()),
+            Err(err) => err,
+        });
+    }
+
+    fn biome_spec(&mut self, project: &Project) -> Result<BiomeSpec, String> {
+        let fields = self.state.biome_text_fields();
+        let id = fields.value("Identifier").to_string();
+        if id.is_empty() {
+            return Err(String::from("The biome identifier can't be empty."));
+        }
+        let name = match fields.value("Display Name") {
+            "" => generator::display_name(&id),
+            name => name.to_string(),
+        };
+        let mut spawns = Vec::new();
+        for row in self.state.biome_spawns().elements() {
+            if !row.trim().is_empty() {
+                spawns.push(Spawn::parse(row)?);
+            }
+        }
+        let mut features = Vec::new();
+        for row in self.state.biome_features().elements() {
+            if !row.trim().is_empty() {
+                features.push(biome::parse_feature(project, row)?);
+            }
+        }
+        let options = self.state.biome_options();
+        let required = |name: &str| {
+            options
+                .color(name)?
+                .ok_or_else(|| format!("{} can't be empty.", name))
+        };
+        let colors = BiomeColors {
+            sky: required("Sky color")?,
+            fog: required("Fog color")?,
+            water: required("Water color")?,
+            water_fog: required("Water fog color")?,
+            grass: options.color("Grass color")?,
+            foliage: options.color("Foliage color")?,
+        };
+        let tags = options
+            .text("Tags")
+            .split(',')
+            .map(str::trim)
+            .filter(|tag| !tag.is_empty())
+            .map(|tag| tag.trim_start_matches('#').to_lowercase())
+            .collect();
+        Ok(BiomeSpec {
+            id,
+            name,
+            temperature: options.float("Temperature")?,
+            downfall: options.float("Downfall")?,
+            precipitation: options.choice("Precipitation").to_string(),
+            colors,
+            spawns,
+            features,
+            caves: options.is_active("Caves"),
+            tags,
+            lang: options.is_active("Generate lang file"),
+        })
+    }
+
+    /// Generates the files of the biome being edited and reports the outcome
+    /// in the status line.
+    pub fn generate_biome(&mut self) {
+        let result = self.project().and_then(|project| {
+            let biome = self.biome_spec(&project)?;
+            generator::biome::generate(&project, &biome).map_err(|err| err.to_string())
+        });
+        self.status = Some(match result {
+            Ok(files) => format!("Generated {} file(s).", files.len()),
+            Err(err) => err,
+        });
+    }
+
+    fn dimension_spec(&mut self) -> Result<DimensionSpec, String> {
+        let id = self
+            .state
+            .dimension_text_fields()
+            .value("Identifier")
+            .to_string();
+        if id.is_empty() {
+            return Err(String::from("The dimension identifier can't be empty."));
+        }
+        let options = self.state.dimension_options();
+        let settings = format!("minecraft:{}", options.choice("Noise settings"));
+        let biome = match options.text("Biome") {
+            "" => return Err(String::from("The biome can't be empty.")),
+            biome if biome.contains(':') => biome.to_string(),
+            biome => format!("minecraft:{}", biome),
+        };
+        let generator = match options.choice("Generator") {
+            "fixed_biome" => Generator::FixedBiome { settings, biome },
+            "flat" => Generator::Flat {
+                biome,
+                layers: Generator::layers(options.text("Flat layers"))?,
+            },
+            _ => Generator::Noise { settings },
+        };
+        let fixed_time = match options.text("Fixed time") {
+            "" => None,
+            time => Some(
+                time.parse()
+                    .map_err(|_| format!("'{}' is not a valid time of day.", time))?,
+            ),
+        };
+        let kind = DimensionType {
+            min_y: options.int("Min Y")?,
+            height: options.int("Height")?,
+            logical_height: options.int("Logical height")?,
+            ambient_light: options.float("Ambient light")?,
+            fixed_time,
+            has_skylight: options.is_active("Has skylight"),
+            has_ceiling: options.is_active("Has ceiling"),
+            ultrawarm: options.is_active("Ultrawarm"),
+            natural: options.is_active("Natural"),
+            coordinate_scale: options.float("Coordinate scale")?,
+            bed_works: options.is_active("Bed works"),
+            respawn_anchor_works: options.is_active("Respawn anchor works"),
+            piglin_safe: options.is_active("Piglin safe"),
+            has_raids: options.is_active("Has raids"),
+            monster_spawn_light_level: options.int("Monster spawn light")?,
+            infiniburn: options.text("Infiniburn").to_string(),
+            effects: options.choice("Effects").to_string(),
+        };
+        Ok(DimensionSpec {
+            id,
+            kind,
+            generator,
+        })
+    }
+
+    /// Generates the files of the dimension being edited and reports the
+    /// outcome in the status line.
+    pub fn generate_dimension(&mut self) {
+        let result = self.project().and_then(|project| {
+            let dimension = self.dimension_spec()?;
+            generator::dimension::generate(&project, &dimension)
+        });
+        self.status = Some(match result {
+            Ok(files) => format!("Generated {} file(s).", files.len()),
+            Err(err) => err,
+        });
+    }
+
+    /// Lists the namespace's advancements as parents and in the tree.
+    pub fn refresh_advancements(&mut self) {
+        if let Ok(project) = self.project() {
+            let mut parents = vec![String::from("none")];
+            parents.extend(advancement::ids(&project));
+            self.state
+                .advancement_options()
+                .set_choices("Parent", parents);
+            *self.state.advancement_tree() = StatefulList::of(advancement::tree(&project));
+        }
+    }
+
+    /// Makes the advancement selected in the tree the parent.
+    pub fn pick_advancement_parent(&mut self) {
+        let id = match self.state.advancement_tree().selected_mut() {
+            Some(row) => row.text.clone(),
+            None => return,
+        };
+        self.state.advancement_options().set_choice("Parent", &id);
+        self.status = Some(format!("The parent is now {}.", id));
+    }
+
+    fn advancement_spec(&mut self, project: &Project) -> Result<AdvancementSpec, String> {
+        let fields = self.state.advancement_text_fields();
+        let id = fields.value("Identifier").trim_matches('/').to_string();
+        if id.is_empty() {
+            return Err(String::from("The advancement identifier can't be empty."));
+        }
+        let title = match fields.value("Title") {
+            "" => generator::display_name(id.rsplit('/').next().unwrap_or_default()),
+            title => title.to_string(),
+        };
+        let description = fields.value("Description").to_string();
+        let mut criteria = Vec::new();
+        for row in self.state.advancement_criteria().elements() {
+            if row.trim().is_empty() {
+                continue;
+            }
+            let criterion = advancement::parse_criterion(project, row)?;
+            if criteria.iter().any(|(name, _)| *name == criterion.0) {
+                return Err(format!("There are two criteria called {}.", criterion.0));
+            }
+            criteria.push(criterion);
+        }
+        if criteria.is_empty() {
+            return Err(String::from("An advancement needs at least one criterion."));
+        }
+        let options = self.state.advancement_options();
+        let parent = match options.choice("Parent") {
+            "none" | "" => None,
+            parent => Some(parent.to_string()),
+        };
+        if parent.as_deref() == Some(project.resource(&id).as_str()) {
+            return Err(String::from("An advancement can't be its own parent."));
+        }
+        let list = |name: &str| -> Vec<String> {
+            options
+                .text(name)
+                .split(',')
+                .map(str::trim)
+                .filter(|entry| !entry.is_empty())
+                .map(|entry| model::location(project, entry))
+                .collect()
+        };
+        let rewards = Rewards {
+            experience: options.int("Experience")? as u32,
+            loot: list("Loot"),
+            recipes: list("Recipes"),
+            function: list("Function").into_iter().next(),
+        };
+        Ok(AdvancementSpec {
+            parent,
+            icon: model::location(project, options.text("Icon")),
+            title,
+            description,
+            frame: options.choice("Frame").to_string(),
+            background: options.text("Background").to_string(),
+            show_toast: options.is_active("Show toast"),
+            announce_to_chat: options.is_active("Announce to chat"),
+            hidden: options.is_active("Hidden"),
+            criteria,
+            any: options.is_active("Any criterion"),
+            rewards,
+            lang: options.is_active("Generate lang file"),
+            id,
+        })
+    }
+
+    /// Generates the advancement being edited, reports the outcome in the
+    /// status line and refreshes the tree.
+    pub fn generate_advancement(&mut self) {
+        let result = self.project().and_then(|project| {
+            let advancement = self.advancement_spec(&project)?;
+            generator::advancement::generate(&project, &advancement).map_err(|err| err.to_string())
+        });
+        self.status = Some(match result {
+            Ok(files) => format!("Generated {} file(s).", files.len()),
+            Err(err) => err,
+        });
+        self.refresh_advancements();
+    }
+
+    fn sound_spec(&mut self, project: &Project) -> Result<SoundSpec, String> {
+        let fields = self.state.sound_text_fields();
+        let id = fields.value("Identifier").to_string();
+        if id.is_empty() {
+            return Err(String::from("The sound event identifier can't be empty."));
+        }
+        let subtitle = fields.value("Subtitle").to_string();
+        let sounds = self
+            .state
+            .sound_files()
+            .elements()
+            .iter()
+            .filter(|row| !row.trim().is_empty())
+            .map(|row| SoundFile::parse(project, row))
+            .collect::<Result<Vec<_>, _>>()?;
+        if sounds.is_empty() {
+            return Err(String::from("A sound event needs at least one sound."));
+        }
+        let options = self.state.sound_options();
+        Ok(SoundSpec {
+            id,
+            subtitle,
+            sounds,
+            replace: options.is_active("Replace"),
+            lang: options.is_active("Generate lang file"),
+            snippet: options.is_active("Registration snippet"),
+        })
+    }
+
+    /// Generates the sound event being edited and reports the outcome in the
+    /// status line, warning about the sounds that have no file yet.
+    pub fn generate_sound(&mut self) {
+        let result = self.project().and_then(|project| {
+            let sound = self.sound_spec(&project)?;
+            let files =
+                generator::sound::generate(&project, &sound).map_err(|err| err.to_string())?;
+            Ok((files, sound.missing_files(&project)))
+        });
+        self.status = Some(match result {
+            Ok((files, missing)) if missing.is_empty() => {
+                format!("Generated {} file(s).", files.len())
+            }
+            Ok((files, missing)) => format!(
+                "Generated {} file(s), these sounds have no file yet: {}.",
+                files.len(),
+                missing.join(", ")
+            ),
+            Err(err) => err,
+        });
+    }
+
+    /// Generates the particle being edited and reports the outcome in the
+    /// status line, with the number of frames that were found.
+    pub fn generate_particle(&mut self) {
+        let id = self
+            .state
+            .particle_text_fields()
+            .value("Identifier")
+            .to_string();
+        let options = self.state.particle_options();
+        let particle = ParticleSpec {
+            always_show: options.is_active("Always show"),
+            snippet: options.is_active("Registration snippet"),
+            id,
+        };
+        let result = self.project().and_then(|project| {
+            if particle.id.is_empty() {
+                return Err(String::from("The particle identifier can't be empty."));
+            }
+            generator::particle::generate(&project, &particle).map_err(|err| err.to_string())
+        });
+        self.status = Some(match result {
+            Ok((files, 0)) => format!(
+                "Generated {} file(s), no frames were found so it uses textures/particle/{}.png.",
+                files.len(),
+                particle.id
+            ),
+            Ok((files, frames)) => {
+                format!(
+                    "Generated {} file(s) with {} frame(s).",
+                    files.len(),
+                    frames
+                )
+            }
+            Err(err) => err,
+        });
+    }
+
+    fn pack_spec(&mut self) -> Result<PackSpec, String> {
+        let description = self
+            .state
+            .pack_text_fields()
+            .value("Description")
+            .to_string();
+        let options = self.state.pack_options();
+        let kind = match options.choice("Pack type") {
+            "resource pack" => PackKind::Resource,
+            _ => PackKind::Data,
+        };
+        let supported_up_to = match options.text("Supported up to") {
+            "" => None,
+            version => Some(
+                GameVersion::parse(version)
+                    .ok_or_else(|| format!("'{}' is not a valid version.", version))?,
+            ),
+        };
+        Ok(PackSpec {
+            kind,
+            description,
+            supported_up_to,
+            icon: options.is_active("Placeholder icon"),
+        })
+    }
+
+    /// Creates the pack being edited in the working directory and makes it
+    /// the folder the generators write into.
+    pub fn generate_pack(&mut self) {
+        let result = self.pack_spec().and_then(|pack| {
+            let folder = match self.state.pack_text_fields().value("Folder") {
+                "" => match pack.kind {
+                    PackKind::Data => format!("{}_data_pack", self.namespace),
+                    PackKind::Resource => format!("{}_resource_pack", self.namespace),
+                },
+                folder => folder.to_string(),
+            };
+            // A single folder name, so the pack stays next to the project
+            let mut components = Path::new(&folder).components();
+            let plain = matches!(components.next(), Some(Component::Normal(_)))
+                && components.next().is_none()
+                && !folder.contains(['/', '\\']);
+            if !plain {
+                return Err(format!(
+                    "'{}' should be a folder name, without slashes or '..'.",
+                    folder
+                ));
+            }
+            let root = self.project_root.join(folder);
+            let version = GameVersion::parse(&self.version)
+                .ok_or_else(|| format!("'{}' is not a valid version.", self.version))?;
+            let project = Project::new(&root, &self.namespace, version, self.loader);
+            let files = generator::pack::generate(&project, &pack)?;
+            Ok((files, root))
+        });
+        self.status = Some(match result {
+            Ok((files, root)) => {
+                let status = format!(
+                    "Created {} file(s), generators now write into {}.",
+                    files.len(),
+                    root.display()
+                );
+                self.root = root;
+                status
+            }
+            Err(err) => err,
+        });
+    }
+
+    /// Zips the pack the generators write into and reports the outcome in the
+    /// status line.
+    pub fn export_pack(&mut self) {
+        let output = export::default_output(&self.root);
+        self.status = Some(match export::zip_pack(&self.root, &output) {
+            Ok(count) => format!("Exported {} file(s) to {}.", count, output.display()),
+            Err(err) => err,
+        });
+    }
+
+    fn function_spec(&mut self, project: &Project) -> Result<FunctionSpec, String> {
+        let path = self
+            .state
+            .function_text_fields()
+            .value("Path")
+            .trim_matches('/')
+            .to_string();
+        if path.is_empty() {
+            return Err(String::from("The function path can't be empty."));
+        }
+        let options = self.state.function_options();
+        let objective = match options.text("Objective") {
+            "" => project.namespace().to_string(),
+            objective => objective.to_string(),
+        };
+        // Objective names were capped at 16 characters until 1.18
+        if !project.version().at_least(18, 0) && objective.chars().count() > 16 {
+            return Err(format!(
+                "The objective '{}' is longer than 16 characters.",
+                objective
+            ));
+        }
+        Ok(FunctionSpec {
+            path,
+            template: options.choice("Template").to_string(),
+            objective,
+            run_on: options.choice("Run on").to_string(),
+        })
+    }
+
+    /// Creates the function being edited and reports the outcome in the
+    /// status line.
+    pub fn generate_function(&mut self) {
+        let result = self.project().and_then(|project| {
+            let function = self.function_spec(&project)?;
+            generator::function::generate(&project, &function).map_err(|err| err.to_string())
+        });
+        self.status = Some(match result {
+            Ok(files) => format!("Generated {} file(s).", files.len()),
+            Err(err) => err,
+        });
+    }
+
+    /// Lints the project's functions into the problem list and sums them up
+    /// in the status line.
+    pub fn lint_functions(&mut self) {
+        match self.project() {
+            Ok(project) => {
+                let problems = check::function::lint(&project);
+                self.status = Some(problems_status(&problems));
+                *self.state.problems() = StatefulList::of(problems);
+                self.state.problems().next();
+            }
+            Err(err) => self.status = Some(err),
+        }
+    }
+
+    /// Checks the references of the namespace's files into the problem list
+    /// and sums them up in the status line.
+    pub fn check_references(&mut self) {
+        match self.project() {
+            Ok(project) => {
+                let problems = check::integrity::check(&project);
+                self.status = Some(problems_status(&problems));
+                *self.state.problems() = StatefulList::of(problems);
+                self.state.problems().next();
+            }
+            Err(err) => self.status = Some(err),
+        }
+    }
+
+    fn rename_spec(&mut self) -> Result<RenameSpec, String> {
+        let fields = self.state.rename_text_fields();
+        let from = fields.value("Identifier").to_string();
+        let to = fields.value("New Identifier").to_string();
+        if from.is_empty() || to.is_empty() {
+            return Err(String::from("Both identifiers are needed."));
+        }
+        if from == to {
+            return Err(String::from(
+                "The new identifier is the same as the old one.",
+            ));
+        }
+        Ok(RenameSpec {
+            from,
+            to,
+            textures: self.state.rename_options().is_active("Rename textures"),
+        })
+    }
+
+    /// Plans the rename and lists every change it makes for review.
+    pub fn preview_rename(&mut self) {
+        let result = self.project().and_then(|project| {
+            let rename = self.rename_spec()?;
+            refactor::rename::plan(&project, &rename)
+        });
+        match result {
+            Ok(changes) if changes.is_empty() => {
+                self.status = Some(String::from("Nothing in the project uses this id."));
+            }
+            Ok(changes) => {
+                self.status = Some(format!("{} file(s) will change.", changes.len()));
+                *self.state.changes() = StatefulList::of(changes);
+                self.state.changes().next();
+                self.set_mode(EditMode::ChangePreview);
+            }
+            Err(err) => self.status = Some(err),
+        }
+    }
+
+    /// Makes the previewed rename's changes.
+    pub fn apply_rename(&mut self) {
+        self.set_mode(EditMode::None);
+        let changes = std::mem::replace(self.state.changes(), StatefulList::of(vec![]));
+        let result = self.project().and_then(|project| {
+            refactor::apply(&project, changes.elements()).map_err(|err| err.to_string())
+        });
+        self.status = Some(match result {
+            Ok(count) => format!(
+                "Changed {} file(s). Rename it where your code registers it too.",
+                count
+            ),
+            Err(err) => err,
+        });
+    }
+
+    /// Lists the namespace's unused assets, none of them picked for deletion.
+    pub fn find_unused(&mut self) {
+        let result = self
+            .project()
+            .and_then(|project| check::unused::find(&project));
+        match result {
+            Ok(unused) => {
+                self.status = Some(match unused.len() {
+                    0 => String::from("Everything is in use."),
+                    count => format!("Found {} unused asset(s).", count),
+                });
+                *self.state.unused() = StatefulList::of(unused);
+                self.state.unused().next();
+            }
+            Err(err) => {
+                *self.state.unused() = StatefulList::of(vec![]);
+                self.status = Some(err);
+            }
+        }
+    }
+
+    /// Asks to confirm the deletion of the picked assets.
+    pub fn confirm_delete(&mut self) {
+        if self.state.unused().picked().is_empty() {
+            self.status = Some(String::from("Pick the assets to delete with Space first."));
+        } else {
+            self.set_mode(EditMode::ConfirmDelete);
+        }
+    }
+
+    /// Deletes the picked assets and lists what is still unused.
+    pub fn delete_unused(&mut self) {
+        self.set_mode(EditMode::None);
+        let result = self.project().and_then(|project| {
+            check::unused::delete(&project, &self.state.unused().picked())
+                .map_err(|err| err.to_string())
+        });
+        match result {
+            Ok(count) => {
+                self.find_unused();
+                let left = self.state.unused().elements().len();
+                self.status = Some(format!("Deleted {} unused asset(s), {} left.", count, left));
+            }
+            Err(err) => self.status = Some(err),
+        }
+    }
+
+    /// Lists the models in the project as parents the item can pick.
+    pub fn refresh_model_parents(&mut self) {
+        if let Ok(project) = self.project() {
+            let parents = model::parents(&project);
+            self.state
+                .item_options()
+                .set_choices("Model parent", parents);
+        }
+    }
+
+    /// Generates the files of the item being edited and reports the outcome
+    /// in the status line.
+    pub fn generate_item(&mut self) {
+        let result = self.project().and_then(|project| {
+            let item = self.item_spec(&project)?;
+            generator::item::generate(&project, &item).map_err(|err| err.to_string())
+        });
+        self.status = Some(match result {
+            Ok(files) => format!("Generated {} file(s).", files.len()),
+            Err(err) => err,
+        });
+    }
+}
+
+/// The option editing a feature field, typed after its kind.
+fn field_option(field: &Field) -> ItemOption<'static> {
+    match field.kind {
+        FieldKind::Int { min, max } => {
+            let value = field.default.parse().unwrap_or(min);
+            ItemOption::int(field.path, field.desc, value, min, max)
+        }
+        FieldKind::Float { min, max } => {
+            let value = field.default.parse().unwrap_or(min);
+            ItemOption::float(field.path, field.desc, value, min, max)
+        }
+        FieldKind::Bool if field.default == "true" => ItemOption::active(field.path, field.desc),
+        FieldKind::Bool => ItemOption::new(field.path, field.desc),
+        _ => ItemOption::text(field.path, field.desc, field.default),
+    }
+}
+
+/// How many problems were found, and in how many files.
+fn problems_status(problems: &[Problem]) -> String {
+    let mut files: Vec<&PathBuf> = problems.iter().map(|problem| &problem.path).collect();
+    files.dedup();
+    match problems.len() {
+        0 => String::from("No problems found."),
+        count => format!("Found {} problem(s) in {} file(s).", count, files.len()),
+    }
 }
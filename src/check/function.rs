@@ -0,0 +1,409 @@
+use std::{collections::BTreeSet, fs, path::Path};
+
+use crate::{
+    check::Problem,
+    project::{self, Project},
+};
+
+/// Commands the 1.13 rewrite removed.
+const REMOVED_COMMANDS: [&str; 8] = [
+    "achievement",
+    "blockdata",
+    "entitydata",
+    "stats",
+    "testfor",
+    "testforblock",
+    "testforblocks",
+    "toggledownfall",
+];
+
+/// Selector arguments 1.13 replaced, with their replacement.
+const OLD_SELECTOR_ARGUMENTS: [(&str, &str); 10] = [
+    ("r", "distance"),
+    ("rm", "distance"),
+    ("c", "limit"),
+    ("m", "gamemode"),
+    ("l", "level"),
+    ("lm", "level"),
+    ("rx", "x_rotation"),
+    ("rxm", "x_rotation"),
+    ("ry", "y_rotation"),
+    ("rym", "y_rotation"),
+];
+
+/// The functions and function tags of every namespace of the project.
+struct Functions {
+    ids: BTreeSet<String>,
+    tags: BTreeSet<String>,
+}
+
+/// The namespaces with a folder in `data`.
+fn namespaces(project: &Project) -> Vec<String> {
+    let mut namespaces: Vec<String> = fs::read_dir(project.resources().join("data"))
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter(|entry| entry.path().is_dir())
+        .map(|entry| entry.file_name().to_string_lossy().into_owned())
+        .collect();
+    namespaces.sort();
+    namespaces
+}
+
+/// The `namespace:path` ids of the files under `dir`.
+fn ids(namespace: &str, dir: &Path, extension: &str) -> Vec<String> {
+    project::list_files(dir, extension)
+        .iter()
+        .filter_map(|path| {
+            path.with_extension("")
+                .strip_prefix(dir)
+                .ok()
+                .map(Path::to_path_buf)
+        })
+        .map(|path| {
+            format!(
+                "{}:{}",
+                namespace,
+                path.to_string_lossy().replace('\\', "/")
+            )
+        })
+        .collect()
+}
+
+/// The problems with the selectors of `command`, like whitespace before
+/// the closing bracket or pre-1.13 arguments.
+fn lint_selectors(command: &str, problems: &mut Vec<String>) {
+    let chars: Vec<char> = command.chars().collect();
+    let mut idx = 0;
+    while idx + 1 < chars.len() {
+        if chars[idx] != '@' || !"aenprs".contains(chars[idx + 1]) {
+            idx += 1;
+            continue;
+        }
+        let selector = format!("@{}", chars[idx + 1]);
+        let mut start = idx + 2;
+        while start < chars.len() && chars[start] == ' ' {
+            start += 1;
+        }
+        if start == chars.len() || chars[start] != '[' {
+            idx += 2;
+            continue;
+        }
+        if start > idx + 2 {
+            problems.push(format!("Selector {} has whitespace before '['.", selector));
+        }
+        // Arguments may hold NBT with brackets of its own
+        let mut depth = 0;
+        let mut end = start;
+        for (pos, c) in chars.iter().enumerate().skip(start) {
+            match c {
+                '[' | '{' => depth += 1,
+                ']' | '}' => depth -= 1,
+                _ => (),
+            }
+            if depth == 0 {
+                end = pos;
+                break;
+            }
+        }
+        if end == start {
+            problems.push(format!("Selector {} is missing its ']'.", selector));
+            return;
+        }
+        let arguments: String = chars[start + 1..end].iter().collect();
+        if arguments.ends_with(char::is_whitespace) {
+            problems.push(format!("Selector {} has whitespace before ']'.", selector));
+        }
+        for argument in arguments.split(',') {
+            let (key, value) = argument.split_once('=').unwrap_or((argument, ""));
+            let key = key.trim();
+            if let Some((_, new)) = OLD_SELECTOR_ARGUMENTS.iter().find(|(old, _)| *old == key) {
+                problems.push(format!("'{}=' is pre-1.13 syntax, use '{}='.", key, new));
+            } else if key.starts_with("score_") {
+                problems.push(format!("'{}=' is pre-1.13 syntax, use 'scores='.", key));
+            } else if key == "type" && value.chars().any(|c| c.is_ascii_uppercase()) {
+                problems.push(format!(
+                    "Entity type '{}' uses a pre-1.13 name, ids are lowercase.",
+                    value.trim()
+                ));
+            }
+        }
+        idx = end + 1;
+    }
+}
+
+/// The problems of a single line of a function.
+fn lint_line(project: &Project, functions: &Functions, line: &str) -> Vec<String> {
+    let mut problems = Vec::new();
+    let line = line.trim_start();
+    if line.is_empty() || line.starts_with('#') {
+        return problems;
+    }
+    let macro_line = line.starts_with('$');
+    let uses_arguments = line.contains("$(");
+    if macro_line && !project.version().at_least(20, 2) {
+        problems.push(String::from("Macro lines need 1.20.2 or later."));
+    } else if macro_line && !uses_arguments {
+        problems.push(String::from("Macro line has no $(argument) to fill in."));
+    } else if !macro_line && uses_arguments {
+        problems.push(String::from(
+            "Line uses a $(argument) but doesn't start with '$'.",
+        ));
+    }
+    let command = line.trim_start_matches('$');
+    if command.starts_with('/') {
+        problems.push(String::from("Commands in functions can't start with '/'."));
+    }
+    let words: Vec<&str> = command.split_whitespace().collect();
+    match words.as_slice() {
+        [first, ..] if REMOVED_COMMANDS.contains(first) => {
+            problems.push(format!("'{}' was removed in 1.13.", first));
+        }
+        ["execute", selector, ..] if selector.starts_with('@') => {
+            problems.push(String::from(
+                "'execute <selector> <x y z>' is pre-1.13 syntax, use 'execute as ... at ... run'.",
+            ));
+        }
+        _ => (),
+    }
+    lint_selectors(command, &mut problems);
+    for pair in words.windows(2) {
+        let target = pair[1];
+        let is_id = target
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || "_-./:#".contains(c));
+        // Text that happens to follow the word, or ids built from macro
+        // arguments, can't be checked
+        if pair[0] != "function" || !is_id {
+            continue;
+        }
+        let (tag, id) = match target.strip_prefix('#') {
+            Some(id) => (true, id),
+            None => (false, target),
+        };
+        let Some((namespace, _)) = id.split_once(':') else {
+            problems.push(format!(
+                "'{}' has no namespace, so it points to minecraft:{}.",
+                target, id
+            ));
+            continue;
+        };
+        if namespace != project.namespace() && namespace != "minecraft" {
+            problems.push(format!(
+                "'{}' is in namespace '{}', not '{}'.",
+                target,
+                namespace,
+                project.namespace()
+            ));
+        }
+        let known = if tag { &functions.tags } else { &functions.ids };
+        if !known.contains(id) {
+            problems.push(format!("'{}' doesn't exist.", target));
+        }
+    }
+    problems
+}
+
+/// Lints every `.mcfunction` file of every namespace in the project for
+/// missing functions, wrong namespaces, pre-1.13 syntax, badly spaced
+/// selectors and misused macro lines.
+pub fn lint(project: &Project) -> Vec<Problem> {
+    let version = project.version();
+    let mut functions = Functions {
+        ids: BTreeSet::new(),
+        tags: BTreeSet::new(),
+    };
+    let mut files = Vec::new();
+    for namespace in namespaces(project) {
+        let data = project.data_of(&namespace);
+        let dir = data.join(version.data_folder("function"));
+        functions.ids.extend(ids(&namespace, &dir, "mcfunction"));
+        files.extend(project::list_files(&dir, "mcfunction"));
+        let tags = data.join(version.data_folder("tags/function"));
+        functions.tags.extend(ids(&namespace, &tags, "json"));
+    }
+    let mut problems = Vec::new();
+    for path in files {
+        let contents = match fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(err) => {
+                problems.push(Problem::new(&path, None, err.to_string()));
+                continue;
+            }
+        };
+        for (idx, line) in contents.lines().enumerate() {
+            for message in lint_line(project, &functions, line) {
+                problems.push(Problem::new(&path, Some(idx + 1), message));
+            }
+        }
+    }
+    problems
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{loader::Loader, version::GameVersion};
+
+    fn project(minor: u32, patch: u32) -> Project {
+        Project::new(
+            Path::new("ruby_mod"),
+            "ruby",
+            GameVersion::new(minor, patch),
+            Loader::Forge,
+        )
+    }
+
+    fn functions() -> Functions {
+        Functions {
+            ids: BTreeSet::from([String::from("ruby:init"), String::from("minecraft:tick")]),
+            tags: BTreeSet::from([String::from("ruby:setup")]),
+        }
+    }
+
+    fn selectors(command: &str) -> Vec<String> {
+        let mut problems = Vec::new();
+        lint_selectors(command, &mut problems);
+        problems
+    }
+
+    fn line(line: &str) -> Vec<String> {
+        lint_line(&project(20, 4), &functions(), line)
+    }
+
+    #[test]
+    fn valid_selectors() {
+        assert!(selectors("kill @e[type=minecraft:zombie,limit=1]").is_empty());
+        assert!(selectors("say @a and @s").is_empty());
+        assert!(selectors("tell player@example.com hi").is_empty());
+    }
+
+    #[test]
+    fn nested_nbt_brackets() {
+        let command = "kill @e[nbt={Tags:[\"a\",\"b\"],Items:[{Count:1b}]},limit=1]";
+        assert!(selectors(command).is_empty());
+        assert_eq!(
+            selectors("kill @e[nbt={Items:[{}]} ] @s"),
+            vec!["Selector @e has whitespace before ']'."]
+        );
+    }
+
+    #[test]
+    fn whitespace_around_brackets() {
+        assert_eq!(
+            selectors("kill @e [type=zombie]"),
+            vec!["Selector @e has whitespace before '['."]
+        );
+        assert_eq!(
+            selectors("kill @e[type=zombie ]"),
+            vec!["Selector @e has whitespace before ']'."]
+        );
+    }
+
+    #[test]
+    fn missing_closing_bracket() {
+        assert_eq!(
+            selectors("kill @e[type=zombie"),
+            vec!["Selector @e is missing its ']'."]
+        );
+    }
+
+    #[test]
+    fn pre_1_13_arguments() {
+        assert_eq!(
+            selectors("kill @e[r=10,c=1]"),
+            vec![
+                "'r=' is pre-1.13 syntax, use 'distance='.",
+                "'c=' is pre-1.13 syntax, use 'limit='.",
+            ]
+        );
+        assert_eq!(
+            selectors("say @a[score_x_min=1]"),
+            vec!["'score_x_min=' is pre-1.13 syntax, use 'scores='."]
+        );
+        assert_eq!(
+            selectors("kill @e[type=Zombie]"),
+            vec!["Entity type 'Zombie' uses a pre-1.13 name, ids are lowercase."]
+        );
+    }
+
+    #[test]
+    fn comments_and_blank_lines() {
+        assert!(line("").is_empty());
+        assert!(line("   # kill @e [r=1]").is_empty());
+    }
+
+    #[test]
+    fn macro_lines() {
+        let macros = project(20, 2);
+        let lint = |text| lint_line(&macros, &functions(), text);
+        assert!(lint("$say $(name)").is_empty());
+        assert_eq!(
+            lint("$say hello"),
+            vec!["Macro line has no $(argument) to fill in."]
+        );
+        assert_eq!(
+            lint("say $(name)"),
+            vec!["Line uses a $(argument) but doesn't start with '$'."]
+        );
+        assert_eq!(
+            lint_line(&project(20, 1), &functions(), "$say $(name)"),
+            vec!["Macro lines need 1.20.2 or later."]
+        );
+    }
+
+    #[test]
+    fn old_commands() {
+        assert_eq!(
+            line("/say hi"),
+            vec!["Commands in functions can't start with '/'."]
+        );
+        assert_eq!(line("testfor @a"), vec!["'testfor' was removed in 1.13."]);
+        assert_eq!(
+            line("execute @a ~ ~ ~ say hi"),
+            vec![
+                "'execute <selector> <x y z>' is pre-1.13 syntax, use 'execute as ... at ... run'."
+            ]
+        );
+    }
+
+    #[test]
+    fn function_references() {
+        assert!(line("function ruby:init").is_empty());
+        assert!(line("function #ruby:setup").is_empty());
+        assert!(line("function minecraft:tick").is_empty());
+        assert_eq!(
+            line("function #ruby:missing"),
+            vec!["'#ruby:missing' doesn't exist."]
+        );
+        assert_eq!(
+            line("function #ruby:init"),
+            vec!["'#ruby:init' doesn't exist."]
+        );
+        assert_eq!(
+            line("function other:init"),
+            vec![
+                "'other:init' is in namespace 'other', not 'ruby'.",
+                "'other:init' doesn't exist.",
+            ]
+        );
+    }
+
+    #[test]
+    fn ids_without_namespace() {
+        assert_eq!(
+            line("function init"),
+            vec!["'init' has no namespace, so it points to minecraft:init."]
+        );
+        assert_eq!(
+            line("function #setup"),
+            vec!["'#setup' has no namespace, so it points to minecraft:setup."]
+        );
+    }
+
+    #[test]
+    fn macro_built_ids_are_skipped() {
+        let macros = project(20, 2);
+        assert!(lint_line(&macros, &functions(), "$function ruby:$(name)").is_empty());
+    }
+}
@@ -0,0 +1,214 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde_json::Value;
+
+use crate::{
+    check::{self, own_path, Assets, JsonFile, Problem},
+    generator::{item_model, recipe},
+    project::Project,
+};
+
+/// The registries whose tags are checked, with what their entries are
+/// looked up in.
+const TAGGED_REGISTRIES: [&str; 4] = ["block", "item", "function", "worldgen/biome"];
+
+/// The namespace's ids of everything that can be referenced, by registry.
+struct Known {
+    items: BTreeSet<String>,
+    blocks: BTreeSet<String>,
+    functions: BTreeSet<String>,
+    biomes: BTreeSet<String>,
+    /// The tag paths of each of `TAGGED_REGISTRIES`.
+    tags: BTreeMap<&'static str, BTreeSet<String>>,
+}
+
+impl Known {
+    fn registry(&self, registry: &str) -> &BTreeSet<String> {
+        match registry {
+            "block" => &self.blocks,
+            "function" => &self.functions,
+            "worldgen/biome" => &self.biomes,
+            _ => &self.items,
+        }
+    }
+
+    /// Whether the item or `#tag` exists, or can't be checked since it's
+    /// from another namespace.
+    fn has(&self, project: &Project, registry: &str, id: &str) -> bool {
+        let (ids, id) = match id.strip_prefix('#') {
+            Some(tag) => (self.tags.get(registry), tag),
+            None => (Some(self.registry(registry)), id),
+        };
+        match (own_path(project, id), ids) {
+            (Some(path), Some(ids)) => ids.contains(path),
+            _ => true,
+        }
+    }
+}
+
+fn check_models(project: &Project, assets: &Assets, problems: &mut Vec<Problem>) {
+    for model in assets.models.values() {
+        let parent = model.json.get("parent").and_then(Value::as_str);
+        if let Some(parent) = parent {
+            if own_path(project, parent).is_some_and(|path| !assets.models.contains_key(path)) {
+                let message = format!("The parent model {} doesn't exist.", parent);
+                problems.push(Problem::new(&model.path, None, message));
+            }
+        }
+        for texture in check::model_textures(&model.json) {
+            if own_path(project, texture).is_some_and(|path| !assets.textures.contains(path)) {
+                let message = format!("The texture {} doesn't exist.", texture);
+                problems.push(Problem::new(&model.path, None, message));
+            }
+        }
+        let overrides = model.json.get("overrides").unwrap_or(&Value::Null);
+        check_model_refs(
+            project,
+            assets,
+            model,
+            check::strings_at(overrides, "model"),
+            problems,
+        );
+    }
+}
+
+fn check_model_refs(
+    project: &Project,
+    assets: &Assets,
+    file: &JsonFile,
+    models: Vec<&str>,
+    problems: &mut Vec<Problem>,
+) {
+    let mut reported = BTreeSet::new();
+    for model in models {
+        let missing =
+            own_path(project, model).is_some_and(|path| !assets.models.contains_key(path));
+        if missing && reported.insert(model) {
+            let message = format!("The model {} doesn't exist.", model);
+            problems.push(Problem::new(&file.path, None, message));
+        }
+    }
+}
+
+/// The items a loot table drops.
+fn loot_items(table: &Value) -> Vec<&str> {
+    let mut items = Vec::new();
+    let mut pending = vec![table];
+    while let Some(value) = pending.pop() {
+        match value {
+            Value::Object(map) => {
+                let kind = map.get("type").and_then(Value::as_str);
+                if matches!(kind, Some("minecraft:item" | "item")) {
+                    items.extend(map.get("name").and_then(Value::as_str));
+                }
+                pending.extend(map.values());
+            }
+            Value::Array(values) => pending.extend(values),
+            _ => (),
+        }
+    }
+    items
+}
+
+/// The item a recipe crafts, written as a bare id or an item stack.
+fn recipe_result(recipe: &Value) -> Option<&str> {
+    match recipe.get("result")? {
+        Value::String(id) => Some(id),
+        result => result
+            .get("id")
+            .or_else(|| result.get("item"))
+            .and_then(Value::as_str),
+    }
+}
+
+/// Loads the models, blockstates, recipes, loot tables, tags and lang file
+/// of the namespace and reports what they reference that doesn't exist,
+/// and the items without a name.
+pub fn check(project: &Project) -> Vec<Problem> {
+    let version = project.version();
+    let mut problems = Vec::new();
+    let assets = Assets::load(project, &mut problems);
+    let data = project.data();
+    let mut known = Known {
+        items: assets.items(project),
+        blocks: assets.blockstates.keys().cloned().collect(),
+        functions: check::paths(&project.data_folder("function"), "mcfunction")
+            .into_iter()
+            .map(|(path, _)| path)
+            .collect(),
+        biomes: check::paths(&data.join("worldgen").join("biome"), "json")
+            .into_iter()
+            .map(|(path, _)| path)
+            .collect(),
+        tags: BTreeMap::new(),
+    };
+    let mut tag_files = Vec::new();
+    for registry in TAGGED_REGISTRIES {
+        let dir = data.join(version.data_folder(&format!("tags/{}", registry)));
+        let files = check::read_jsons(&dir, &mut problems);
+        known.tags.insert(registry, files.keys().cloned().collect());
+        tag_files.push((registry, files));
+    }
+
+    check_models(project, &assets, &mut problems);
+    for blockstate in assets.blockstates.values() {
+        let models = check::strings_at(&blockstate.json, "model");
+        check_model_refs(project, &assets, blockstate, models, &mut problems);
+    }
+    for definition in assets.definitions.values() {
+        let models = item_model::models(&definition.json);
+        let models = models.iter().map(String::as_str).collect();
+        check_model_refs(project, &assets, definition, models, &mut problems);
+    }
+
+    let recipes = check::read_jsons(&project.data_folder("recipe"), &mut problems);
+    for file in recipes.values() {
+        let mut items = recipe::ingredients(&file.json);
+        items.extend(recipe_result(&file.json).map(String::from));
+        for item in items {
+            if !known.has(project, "item", &item) {
+                let message = format!("The item {} doesn't exist.", item);
+                problems.push(Problem::new(&file.path, None, message));
+            }
+        }
+    }
+    let loot_tables = check::read_jsons(&project.data_folder("loot_table"), &mut problems);
+    for file in loot_tables.values() {
+        for item in loot_items(&file.json) {
+            if !known.has(project, "item", item) {
+                let message = format!("The item {} doesn't exist.", item);
+                problems.push(Problem::new(&file.path, None, message));
+            }
+        }
+    }
+    for (registry, files) in &tag_files {
+        for file in files.values() {
+            let values = file.json.get("values").and_then(Value::as_array);
+            for value in values.into_iter().flatten() {
+                // Optional entries are written as objects
+                let entry = match value {
+                    Value::String(id) => id.as_str(),
+                    value => value.get("id").and_then(Value::as_str).unwrap_or_default(),
+                };
+                if !known.has(project, registry, entry) {
+                    let message = format!("The {} {} doesn't exist.", registry, entry);
+                    problems.push(Problem::new(&file.path, None, message));
+                }
+            }
+        }
+    }
+
+    let namespace = project.namespace();
+    for item in &known.items {
+        let translated = ["item", "block"].iter().any(|kind| {
+            assets
+                .lang
+                .contains_key(&format!("{}.{}.{}", kind, namespace, item))
+        });
+        if !translated {
+            let message = format!("The item {}:{} has no name.", namespace, item);
+            problems.push(Problem::new(&assets.lang_path, None, message));
+        }
+    }
+    problems
+}
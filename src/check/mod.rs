@@ -0,0 +1,171 @@
+pub mod function;
+pub mod integrity;
+pub mod unused;
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs,
+    path::{Path, PathBuf},
+};
+
+use serde_json::{Map, Value};
+
+use crate::project::{self, Project};
+
+/// Something wrong found in a project file.
+pub struct Problem {
+    pub path: PathBuf,
+    /// The 1-based line, for files read line by line.
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+impl Problem {
+    pub fn new(path: &Path, line: Option<usize>, message: String) -> Self {
+        Self {
+            path: path.to_path_buf(),
+            line,
+            message,
+        }
+    }
+
+    /// `file:line` with the file relative to `root` when it's inside it.
+    pub fn location(&self, root: &Path) -> String {
+        let path = self.path.strip_prefix(root).unwrap_or(&self.path);
+        match self.line {
+            Some(line) => format!("{}:{}", path.display(), line),
+            None => path.display().to_string(),
+        }
+    }
+}
+
+/// The path of `id` when it's in the project's namespace. Ids without a
+/// namespace are vanilla's.
+pub fn own_path<'a>(project: &Project, id: &'a str) -> Option<&'a str> {
+    let (namespace, path) = id.split_once(':')?;
+    (namespace == project.namespace()).then_some(path)
+}
+
+/// Every string found under `key` in `value`, at any depth.
+pub fn strings_at<'a>(value: &'a Value, key: &str) -> Vec<&'a str> {
+    let mut strings = Vec::new();
+    let mut pending = vec![value];
+    while let Some(value) = pending.pop() {
+        match value {
+            Value::Object(map) => {
+                if let Some(Value::String(string)) = map.get(key) {
+                    strings.push(string.as_str());
+                }
+                pending.extend(map.values());
+            }
+            Value::Array(values) => pending.extend(values),
+            _ => (),
+        }
+    }
+    strings
+}
+
+/// The `/` separated paths of the files under `dir` with `extension`,
+/// without it.
+pub fn paths(dir: &Path, extension: &str) -> Vec<(String, PathBuf)> {
+    project::list_files(dir, extension)
+        .into_iter()
+        .filter_map(|file| {
+            let relative = file.with_extension("");
+            let relative = relative.strip_prefix(dir).ok()?;
+            Some((relative.to_string_lossy().replace('\\', "/"), file))
+        })
+        .collect()
+}
+
+/// A JSON file of the project.
+pub struct JsonFile {
+    pub path: PathBuf,
+    pub json: Value,
+}
+
+/// The JSON files under `dir` by their path, the ones that fail to parse
+/// being reported instead.
+pub fn read_jsons(dir: &Path, problems: &mut Vec<Problem>) -> BTreeMap<String, JsonFile> {
+    let mut files = BTreeMap::new();
+    for (id, path) in paths(dir, "json") {
+        let parsed = fs::read_to_string(&path)
+            .map_err(|err| err.to_string())
+            .and_then(|text| serde_json::from_str(&text).map_err(|err| err.to_string()));
+        match parsed {
+            Ok(json) => {
+                files.insert(id, JsonFile { path, json });
+            }
+            Err(err) => problems.push(Problem::new(&path, None, err)),
+        }
+    }
+    files
+}
+
+/// The assets of the project's namespace, loaded once for the checks that
+/// cross reference them.
+pub struct Assets {
+    /// Texture paths under `textures/`, like `block/ruby_ore`.
+    pub textures: BTreeSet<String>,
+    /// Models by their path under `models/`, like `item/ruby`.
+    pub models: BTreeMap<String, JsonFile>,
+    pub blockstates: BTreeMap<String, JsonFile>,
+    /// The 1.21.4 `items/<id>.json` definitions.
+    pub definitions: BTreeMap<String, JsonFile>,
+    pub lang_path: PathBuf,
+    pub lang: Map<String, Value>,
+}
+
+impl Assets {
+    pub fn load(project: &Project, problems: &mut Vec<Problem>) -> Self {
+        let assets = project.assets();
+        let lang_path = assets.join("lang").join("en_us.json");
+        let lang = project::read_json_object(&lang_path).unwrap_or_else(|err| {
+            problems.push(Problem::new(&lang_path, None, err.to_string()));
+            Map::new()
+        });
+        Self {
+            textures: paths(&assets.join("textures"), "png")
+                .into_iter()
+                .map(|(path, _)| path)
+                .collect(),
+            models: read_jsons(&assets.join("models"), problems),
+            blockstates: read_jsons(&assets.join("blockstates"), problems),
+            definitions: read_jsons(&assets.join("items"), problems),
+            lang_path,
+            lang,
+        }
+    }
+
+    /// The ids of the namespace's items: the ones with a definition or an
+    /// item model. Item models whose parent is another of the namespace's
+    /// item models are taken for override models rather than items.
+    pub fn items(&self, project: &Project) -> BTreeSet<String> {
+        let mut items: BTreeSet<String> = self.definitions.keys().cloned().collect();
+        for (path, model) in &self.models {
+            let Some(id) = path.strip_prefix("item/") else {
+                continue;
+            };
+            let parent = model.json.get("parent").and_then(Value::as_str);
+            let sub_model = parent
+                .and_then(|parent| own_path(project, parent))
+                .is_some_and(|parent| parent.starts_with("item/"));
+            if !sub_model {
+                items.insert(id.to_string());
+            }
+        }
+        items
+    }
+}
+
+/// The textures a model uses, leaving out `#variable` references.
+pub fn model_textures(model: &Value) -> Vec<&str> {
+    match model.get("textures") {
+        Some(Value::Object(textures)) => textures
+            .values()
+            .filter_map(Value::as_str)
+            .filter(|texture| !texture.starts_with('#'))
+            .collect(),
+        _ => Vec::new(),
+    }
+}
@@ -0,0 +1,180 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    io,
+    path::PathBuf,
+};
+
+use serde_json::Value;
+
+use crate::{
+    check::{self, own_path, Assets},
+    generator::item_model,
+    project::{self, Project},
+};
+
+/// The texture folders models draw from. Textures anywhere else, like the
+/// entity or GUI ones, are used from code and never listed.
+const MODEL_TEXTURE_FOLDERS: [&str; 2] = ["block/", "item/"];
+
+/// A file or lang key nothing in the namespace uses.
+pub struct Unused {
+    /// The file, or the lang file for a lang key.
+    pub path: PathBuf,
+    /// The lang key, when it's one rather than a whole file.
+    pub key: Option<String>,
+    /// What it is, such as `Texture item/ruby`.
+    pub name: String,
+    /// Whether it's picked for deletion.
+    pub selected: bool,
+}
+
+impl Unused {
+    fn file(path: PathBuf, name: String) -> Self {
+        Self {
+            path,
+            key: None,
+            name,
+            selected: false,
+        }
+    }
+}
+
+/// The own ids among `ids`, as paths.
+fn own_paths<'a>(project: &Project, ids: impl IntoIterator<Item = &'a str>) -> Vec<String> {
+    ids.into_iter()
+        .filter_map(|id| own_path(project, id))
+        .map(String::from)
+        .collect()
+}
+
+/// The models the game loads: the ones blockstates and item definitions
+/// point to, and the parents and override models of those.
+fn used_models(project: &Project, assets: &Assets) -> BTreeSet<String> {
+    let mut pending = Vec::new();
+    for blockstate in assets.blockstates.values() {
+        pending.extend(own_paths(
+            project,
+            check::strings_at(&blockstate.json, "model"),
+        ));
+    }
+    for definition in assets.definitions.values() {
+        let models = item_model::models(&definition.json);
+        pending.extend(own_paths(project, models.iter().map(String::as_str)));
+    }
+    if !project.version().at_least(21, 4) {
+        // Before item definitions, every item used the model named after it
+        pending.extend(
+            assets
+                .items(project)
+                .iter()
+                .map(|id| format!("item/{}", id)),
+        );
+    }
+    let mut used = BTreeSet::new();
+    while let Some(path) = pending.pop() {
+        let Some(model) = assets.models.get(&path) else {
+            continue;
+        };
+        if !used.insert(path) {
+            continue;
+        }
+        let parent = model.json.get("parent").and_then(Value::as_str);
+        let overrides = model.json.get("overrides").unwrap_or(&Value::Null);
+        let models = parent
+            .into_iter()
+            .chain(check::strings_at(overrides, "model"));
+        pending.extend(own_paths(project, models));
+    }
+    used
+}
+
+/// Lists the textures no model uses, the models the game never loads and
+/// the item and block names of ids that no longer exist. Nothing is listed
+/// when an asset can't be read, since what it uses isn't known.
+pub fn find(project: &Project) -> Result<Vec<Unused>, String> {
+    let mut problems = Vec::new();
+    let assets = Assets::load(project, &mut problems);
+    if let Some(problem) = problems.first() {
+        return Err(format!(
+            "{} can't be read, so what it uses isn't known: {}",
+            problem.location(project.root()),
+            problem.message
+        ));
+    }
+    let mut unused = Vec::new();
+
+    let textures: BTreeSet<String> = assets
+        .models
+        .values()
+        .flat_map(|model| own_paths(project, check::model_textures(&model.json)))
+        .collect();
+    let folder = project.assets().join("textures");
+    for texture in &assets.textures {
+        let in_models = MODEL_TEXTURE_FOLDERS
+            .iter()
+            .any(|folder| texture.starts_with(folder));
+        if in_models && !textures.contains(texture) {
+            let path = folder.join(format!("{}.png", texture));
+            unused.push(Unused::file(path, format!("Texture {}", texture)));
+        }
+    }
+
+    let models = used_models(project, &assets);
+    for (path, model) in &assets.models {
+        if !models.contains(path) {
+            unused.push(Unused::file(model.path.clone(), format!("Model {}", path)));
+        }
+    }
+
+    let items = assets.items(project);
+    for key in assets.lang.keys() {
+        // Tooltips and other keys below an id stay as long as the id does
+        let mut parts = key.splitn(4, '.');
+        let (Some(kind), Some(namespace), Some(id)) = (parts.next(), parts.next(), parts.next())
+        else {
+            continue;
+        };
+        let exists = match kind {
+            "item" => items.contains(id),
+            "block" => assets.blockstates.contains_key(id),
+            _ => true,
+        };
+        if namespace == project.namespace() && !exists {
+            unused.push(Unused {
+                path: assets.lang_path.clone(),
+                key: Some(key.clone()),
+                name: format!("Lang key {}", key),
+                selected: false,
+            });
+        }
+    }
+    Ok(unused)
+}
+
+/// Deletes the files and lang keys of `unused`, along with the `.mcmeta`
+/// of animated textures, returning how many were deleted.
+pub fn delete(project: &Project, unused: &[&Unused]) -> io::Result<usize> {
+    let mut keys: BTreeMap<&PathBuf, Vec<&str>> = BTreeMap::new();
+    for entry in unused {
+        match &entry.key {
+            Some(key) => keys.entry(&entry.path).or_default().push(key),
+            None => {
+                let mut meta = entry.path.clone().into_os_string();
+                meta.push(".mcmeta");
+                let meta = PathBuf::from(meta);
+                if meta.is_file() {
+                    project.remove(&meta)?;
+                }
+                project.remove(&entry.path)?;
+            }
+        }
+    }
+    for (path, keys) in keys {
+        let mut lang = project::read_json_object(path)?;
+        for key in keys {
+            lang.remove(key);
+        }
+        project.write_json(path, &Value::Object(lang))?;
+    }
+    Ok(unused.len())
+}
@@ -0,0 +1,79 @@
+use std::{
+    fs::{self, File},
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+use zip::{write::FileOptions, CompressionMethod, DateTime, ZipWriter};
+
+/// The files of the pack at `root` as `/` separated paths relative to it,
+/// sorted so archives come out the same on every platform. Hidden files and
+/// folders, like `.mcdevtools`, are left out.
+fn pack_files(root: &Path) -> io::Result<Vec<(String, PathBuf)>> {
+    let mut files = Vec::new();
+    let mut pending = vec![root.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        for entry in fs::read_dir(&dir)? {
+            let path = entry?.path();
+            let hidden = path
+                .file_name()
+                .is_some_and(|name| name.to_string_lossy().starts_with('.'));
+            if hidden {
+                continue;
+            }
+            if path.is_dir() {
+                pending.push(path);
+            } else if let Ok(relative) = path.strip_prefix(root) {
+                let components: Vec<String> = relative
+                    .components()
+                    .map(|part| part.as_os_str().to_string_lossy().into_owned())
+                    .collect();
+                files.push((components.join("/"), path));
+            }
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Packages the pack at `root` into the zip at `output`, returning the
+/// number of files archived. Every entry gets the same timestamp and
+/// permissions so the same pack always gives the same archive.
+pub fn zip_pack(root: &Path, output: &Path) -> Result<usize, String> {
+    if !root.join("pack.mcmeta").is_file() {
+        return Err(format!(
+            "{} has no pack.mcmeta at its root, so the game wouldn't load it.",
+            root.display()
+        ));
+    }
+    let to_string = |err: io::Error| err.to_string();
+    // Earlier exports written inside the pack don't belong in the archive
+    let files: Vec<(String, PathBuf)> = pack_files(root)
+        .map_err(to_string)?
+        .into_iter()
+        .filter(|(name, _)| !name.ends_with(".zip"))
+        .collect();
+    let options = FileOptions::default()
+        .compression_method(CompressionMethod::Deflated)
+        .last_modified_time(DateTime::default())
+        .unix_permissions(0o644);
+    let mut zip = ZipWriter::new(File::create(output).map_err(to_string)?);
+    for (name, path) in &files {
+        zip.start_file(name.as_str(), options)
+            .map_err(|err| err.to_string())?;
+        zip.write_all(&fs::read(path).map_err(to_string)?)
+            .map_err(to_string)?;
+    }
+    zip.finish().map_err(|err| err.to_string())?;
+    Ok(files.len())
+}
+
+/// The archive a pack is exported to when no path is given, named after the
+/// pack folder and placed next to it.
+pub fn default_output(root: &Path) -> PathBuf {
+    let name = root.file_name().map_or_else(
+        || String::from("pack"),
+        |name| name.to_string_lossy().into_owned(),
+    );
+    root.with_file_name(format!("{}.zip", name))
+}
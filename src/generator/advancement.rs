@@ -0,0 +1,281 @@
+use std::{collections::BTreeMap, io, path::PathBuf};
+
+use serde_json::{json, Map, Value};
+
+use crate::{
+    generator::{item_model::TreeRow, model},
+    project::{self, Project},
+    version::GameVersion,
+};
+
+/// The vanilla triggers criteria can use, with the argument they take.
+pub const TRIGGERS: [&str; 14] = [
+    "inventory_changed <item>",
+    "consume_item <item>",
+    "enchanted_item <item>",
+    "recipe_unlocked <recipe>",
+    "recipe_crafted <recipe>",
+    "player_killed_entity <entity>",
+    "bred_animals <entity>",
+    "changed_dimension <dimension>",
+    "enter_block <block>",
+    "placed_block <block>",
+    "sleep_in_bed",
+    "location",
+    "tick",
+    "impossible",
+];
+
+/// An item predicate matching `item`. 1.20.5 made `items` a single id
+/// instead of a list.
+pub fn item_predicate(version: GameVersion, item: &str) -> Value {
+    if version.at_least(20, 5) {
+        json!({ "items": item })
+    } else {
+        json!({ "items": [item] })
+    }
+}
+
+/// Ids that are nearly always vanilla, like entities and dimensions, are
+/// namespaced as such.
+fn vanilla(id: &str) -> String {
+    if id.contains(':') {
+        id.to_string()
+    } else {
+        format!("minecraft:{}", id)
+    }
+}
+
+/// Parses criteria rows written as `name trigger [argument]`, such as
+/// `get_ruby inventory_changed ruby`, see `TRIGGERS`.
+pub fn parse_criterion(project: &Project, row: &str) -> Result<(String, Value), String> {
+    let words: Vec<&str> = row.split_whitespace().collect();
+    let (name, trigger, arg) = match words.as_slice() {
+        [name, trigger] => (*name, *trigger, None),
+        [name, trigger, arg] => (*name, *trigger, Some(*arg)),
+        _ => {
+            return Err(format!(
+                "Criterion '{}' should be written as 'name trigger [argument]'.",
+                row
+            ))
+        }
+    };
+    let usage = TRIGGERS
+        .iter()
+        .find(|usage| usage.split(' ').next() == Some(trigger))
+        .ok_or_else(|| {
+            let names: Vec<&str> = TRIGGERS
+                .iter()
+                .filter_map(|usage| usage.split(' ').next())
+                .collect();
+            format!("'{}' is not one of {}.", trigger, names.join(", "))
+        })?;
+    let conditions = match (arg, usage.contains('<')) {
+        (None, true) => {
+            return Err(format!(
+                "Criterion '{}' should be '{} {}'.",
+                row, name, usage
+            ))
+        }
+        (Some(_), false) => return Err(format!("'{}' takes no argument.", trigger)),
+        (None, false) => None,
+        (Some(arg), true) => {
+            let version = project.version();
+            let id = model::location(project, arg);
+            Some(match trigger {
+                "inventory_changed" => json!({ "items": [item_predicate(version, &id)] }),
+                "consume_item" | "enchanted_item" => {
+                    json!({ "item": item_predicate(version, &id) })
+                }
+                "recipe_unlocked" => json!({ "recipe": id }),
+                "recipe_crafted" => json!({ "recipe_id": id }),
+                "player_killed_entity" => json!({ "entity": { "type": vanilla(arg) } }),
+                "bred_animals" => json!({ "child": { "type": vanilla(arg) } }),
+                "changed_dimension" => json!({ "to": vanilla(arg) }),
+                "placed_block" if version.at_least(20, 0) => json!({
+                    "location": [{
+                        "condition": "minecraft:block_state_property",
+                        "block": id,
+                    }]
+                }),
+                _ => json!({ "block": id }),
+            })
+        }
+    };
+    let mut criterion = Map::new();
+    criterion.insert("trigger".into(), json!(format!("minecraft:{}", trigger)));
+    if let Some(conditions) = conditions {
+        criterion.insert("conditions".into(), conditions);
+    }
+    Ok((name.to_string(), Value::Object(criterion)))
+}
+
+/// What the player gets for completing the advancement.
+#[derive(Default)]
+pub struct Rewards {
+    pub experience: u32,
+    pub loot: Vec<String>,
+    pub recipes: Vec<String>,
+    pub function: Option<String>,
+}
+
+/// Everything needed to generate an advancement.
+pub struct AdvancementSpec {
+    /// The path inside the advancement folder, such as `story/find_ruby`.
+    pub id: String,
+    /// `None` for the root of a tab.
+    pub parent: Option<String>,
+    pub icon: String,
+    pub title: String,
+    pub description: String,
+    /// `task`, `goal` or `challenge`.
+    pub frame: String,
+    /// The tab background, only used by roots.
+    pub background: String,
+    pub show_toast: bool,
+    pub announce_to_chat: bool,
+    pub hidden: bool,
+    pub criteria: Vec<(String, Value)>,
+    /// Whether any criterion completes it instead of all of them.
+    pub any: bool,
+    pub rewards: Rewards,
+    pub lang: bool,
+}
+
+impl AdvancementSpec {
+    fn lang_key(&self, project: &Project, field: &str) -> String {
+        format!(
+            "advancements.{}.{}.{}",
+            project.namespace(),
+            self.id.replace('/', "."),
+            field
+        )
+    }
+
+    fn json(&self, project: &Project) -> Value {
+        let icon = if project.version().at_least(20, 5) {
+            json!({ "id": self.icon })
+        } else {
+            json!({ "item": self.icon })
+        };
+        let text = |field: &str, text: &str| {
+            if self.lang {
+                json!({ "translate": self.lang_key(project, field) })
+            } else {
+                json!(text)
+            }
+        };
+        let mut display = Map::new();
+        display.insert("icon".into(), icon);
+        display.insert("title".into(), text("title", &self.title));
+        display.insert("description".into(), text("description", &self.description));
+        display.insert("frame".into(), json!(self.frame));
+        if self.parent.is_none() {
+            display.insert("background".into(), json!(self.background));
+        }
+        display.insert("show_toast".into(), json!(self.show_toast));
+        display.insert("announce_to_chat".into(), json!(self.announce_to_chat));
+        display.insert("hidden".into(), json!(self.hidden));
+
+        let mut advancement = Map::new();
+        if let Some(parent) = &self.parent {
+            advancement.insert("parent".into(), json!(parent));
+        }
+        advancement.insert("display".into(), Value::Object(display));
+        let criteria: Map<String, Value> = self.criteria.iter().cloned().collect();
+        advancement.insert("criteria".into(), Value::Object(criteria));
+        if self.any && self.criteria.len() > 1 {
+            let names: Vec<&String> = self.criteria.iter().map(|(name, _)| name).collect();
+            advancement.insert("requirements".into(), json!([names]));
+        }
+        let rewards = &self.rewards;
+        let mut json_rewards = Map::new();
+        if rewards.experience > 0 {
+            json_rewards.insert("experience".into(), json!(rewards.experience));
+        }
+        if !rewards.loot.is_empty() {
+            json_rewards.insert("loot".into(), json!(rewards.loot));
+        }
+        if !rewards.recipes.is_empty() {
+            json_rewards.insert("recipes".into(), json!(rewards.recipes));
+        }
+        if let Some(function) = &rewards.function {
+            json_rewards.insert("function".into(), json!(function));
+        }
+        if !json_rewards.is_empty() {
+            advancement.insert("rewards".into(), Value::Object(json_rewards));
+        }
+        Value::Object(advancement)
+    }
+}
+
+/// Writes the advancement and its translations, returning the files that
+/// were written.
+pub fn generate(project: &Project, advancement: &AdvancementSpec) -> io::Result<Vec<PathBuf>> {
+    let path = project
+        .data_folder("advancement")
+        .join(format!("{}.json", advancement.id));
+    let mut written = vec![project.write_json(&path, &advancement.json(project))?];
+    if advancement.lang {
+        for (field, text) in [
+            ("title", &advancement.title),
+            ("description", &advancement.description),
+        ] {
+            let key = advancement.lang_key(project, field);
+            written.push(project.add_translation(&key, text)?);
+        }
+        written.dedup();
+    }
+    Ok(written)
+}
+
+/// The advancements of the namespace, each with the parent it names if any.
+fn advancements(project: &Project) -> BTreeMap<String, Option<String>> {
+    let dir = project.data_folder("advancement");
+    let mut advancements = BTreeMap::new();
+    for path in project::list_files(&dir, "json") {
+        let id = match path.with_extension("").strip_prefix(&dir) {
+            Ok(id) => project.resource(&id.to_string_lossy().replace('\\', "/")),
+            Err(_) => continue,
+        };
+        let parent = project::read_json_object(&path)
+            .ok()
+            .and_then(|json| json.get("parent").and_then(Value::as_str).map(String::from));
+        advancements.insert(id, parent);
+    }
+    advancements
+}
+
+/// The ids of the namespace's advancements, for picking a parent.
+pub fn ids(project: &Project) -> Vec<String> {
+    advancements(project).into_keys().collect()
+}
+
+/// The namespace's advancements nested under their parents. Advancements
+/// whose parent is outside the namespace are shown as roots, the ones in a
+/// parent loop are left out since they never reach a root.
+pub fn tree(project: &Project) -> Vec<TreeRow> {
+    let advancements = advancements(project);
+    let mut children: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+    let mut roots = Vec::new();
+    for (id, parent) in &advancements {
+        match parent {
+            Some(parent) if advancements.contains_key(parent) => {
+                children.entry(parent).or_default().push(id)
+            }
+            _ => roots.push(id.as_str()),
+        }
+    }
+    let mut rows = Vec::new();
+    let mut pending: Vec<(usize, &str)> = roots.into_iter().rev().map(|id| (0, id)).collect();
+    while let Some((depth, id)) = pending.pop() {
+        rows.push(TreeRow {
+            depth,
+            text: id.to_string(),
+        });
+        if let Some(ids) = children.get(id) {
+            pending.extend(ids.iter().rev().map(|child| (depth + 1, *child)));
+        }
+    }
+    rows
+}
@@ -0,0 +1,220 @@
+use std::{io, path::PathBuf};
+
+use serde_json::{json, Map, Value};
+
+use crate::{generator::model, project::Project};
+
+/// The generation steps features are placed in, in the order of the
+/// biome's `features` lists.
+pub const STEPS: [&str; 11] = [
+    "raw_generation",
+    "lakes",
+    "local_modifications",
+    "underground_structures",
+    "surface_structures",
+    "strongholds",
+    "underground_ores",
+    "underground_decoration",
+    "fluid_springs",
+    "vegetal_decoration",
+    "top_layer_modification",
+];
+
+pub const SPAWN_CATEGORIES: [&str; 8] = [
+    "monster",
+    "creature",
+    "ambient",
+    "axolotls",
+    "underground_water_creature",
+    "water_creature",
+    "water_ambient",
+    "misc",
+];
+
+/// The vanilla carvers added when the biome has caves.
+const CAVE_CARVERS: [&str; 3] = [
+    "minecraft:cave",
+    "minecraft:cave_extra_underground",
+    "minecraft:canyon",
+];
+
+/// A mob spawning in the biome.
+pub struct Spawn {
+    category: String,
+    entity: String,
+    weight: u32,
+    min_count: u32,
+    max_count: u32,
+}
+
+impl Spawn {
+    /// Parses rows written as `category entity weight min max`, such as
+    /// `monster zombie 95 4 4`. Entities without a namespace are vanilla.
+    pub fn parse(row: &str) -> Result<Self, String> {
+        let invalid = || {
+            format!(
+                "Spawn '{}' should be written as 'category entity weight min max'.",
+                row
+            )
+        };
+        let words: Vec<&str> = row.split_whitespace().collect();
+        let [category, entity, weight, min, max] = words.as_slice() else {
+            return Err(invalid());
+        };
+        if !SPAWN_CATEGORIES.contains(category) {
+            return Err(format!(
+                "'{}' is not one of {}.",
+                category,
+                SPAWN_CATEGORIES.join(", ")
+            ));
+        }
+        let number = |word: &str| word.parse::<u32>().map_err(|_| invalid());
+        let (min_count, max_count) = (number(min)?, number(max)?);
+        if min_count > max_count {
+            return Err(format!("Spawn '{}' has a min count above its max.", row));
+        }
+        Ok(Self {
+            category: category.to_string(),
+            entity: if entity.contains(':') {
+                entity.to_string()
+            } else {
+                format!("minecraft:{}", entity)
+            },
+            weight: number(weight)?,
+            min_count,
+            max_count,
+        })
+    }
+}
+
+/// Parses feature rows written as `step feature`, such as
+/// `vegetal_decoration bush`, into the step index and placed feature.
+pub fn parse_feature(project: &Project, row: &str) -> Result<(usize, String), String> {
+    let words: Vec<&str> = row.split_whitespace().collect();
+    let [step, feature] = words.as_slice() else {
+        return Err(format!(
+            "Feature '{}' should be written as 'step placed_feature'.",
+            row
+        ));
+    };
+    let step = STEPS
+        .iter()
+        .position(|s| s == step)
+        .ok_or_else(|| format!("'{}' is not one of {}.", step, STEPS.join(", ")))?;
+    Ok((step, model::location(project, feature)))
+}
+
+/// The colors of the sky, fog, water and plants.
+pub struct BiomeColors {
+    pub sky: i64,
+    pub fog: i64,
+    pub water: i64,
+    pub water_fog: i64,
+    /// Left to the temperature and downfall when `None`.
+    pub grass: Option<i64>,
+    pub foliage: Option<i64>,
+}
+
+/// Everything needed to generate a biome.
+pub struct BiomeSpec {
+    pub id: String,
+    pub name: String,
+    pub temperature: f64,
+    pub downfall: f64,
+    /// `rain`, `snow` or `none`.
+    pub precipitation: String,
+    pub colors: BiomeColors,
+    pub spawns: Vec<Spawn>,
+    pub features: Vec<(usize, String)>,
+    pub caves: bool,
+    /// Biome tags such as `is_overworld`, vanilla unless namespaced.
+    pub tags: Vec<String>,
+    pub lang: bool,
+}
+
+impl BiomeSpec {
+    fn json(&self, project: &Project) -> Value {
+        let version = project.version();
+        let mut biome = Map::new();
+        // Whether it snows or rains follows the temperature from 1.19.4
+        if version.at_least(19, 4) {
+            biome.insert(
+                "has_precipitation".into(),
+                json!(self.precipitation != "none"),
+            );
+        } else {
+            biome.insert("precipitation".into(), json!(self.precipitation));
+        }
+        biome.insert("temperature".into(), json!(self.temperature));
+        biome.insert("downfall".into(), json!(self.downfall));
+        let colors = &self.colors;
+        let mut effects = Map::new();
+        effects.insert("sky_color".into(), json!(colors.sky));
+        effects.insert("fog_color".into(), json!(colors.fog));
+        effects.insert("water_color".into(), json!(colors.water));
+        effects.insert("water_fog_color".into(), json!(colors.water_fog));
+        if let Some(grass) = colors.grass {
+            effects.insert("grass_color".into(), json!(grass));
+        }
+        if let Some(foliage) = colors.foliage {
+            effects.insert("foliage_color".into(), json!(foliage));
+        }
+        biome.insert("effects".into(), Value::Object(effects));
+        let mut spawners = Map::new();
+        for spawn in &self.spawns {
+            let entries = spawners
+                .entry(spawn.category.clone())
+                .or_insert_with(|| json!([]));
+            if let Value::Array(entries) = entries {
+                entries.push(json!({
+                    "type": spawn.entity,
+                    "weight": spawn.weight,
+                    "minCount": spawn.min_count,
+                    "maxCount": spawn.max_count,
+                }));
+            }
+        }
+        biome.insert("spawners".into(), Value::Object(spawners));
+        biome.insert("spawn_costs".into(), json!({}));
+        let carvers: Vec<&str> = if self.caves {
+            CAVE_CARVERS.to_vec()
+        } else {
+            Vec::new()
+        };
+        // 1.21.2 dropped the liquid carving step, leaving a single list
+        let carvers = if version.at_least(21, 2) {
+            json!(carvers)
+        } else {
+            json!({ "air": carvers })
+        };
+        biome.insert("carvers".into(), carvers);
+        let mut steps = vec![Vec::new(); STEPS.len()];
+        for (step, feature) in &self.features {
+            steps[*step].push(feature.clone());
+        }
+        biome.insert("features".into(), json!(steps));
+        Value::Object(biome)
+    }
+}
+
+/// Writes the biome, its translation and the tags it was added to,
+/// returning the files that were written.
+pub fn generate(project: &Project, biome: &BiomeSpec) -> io::Result<Vec<PathBuf>> {
+    let mut written = Vec::new();
+    let path = project
+        .data()
+        .join("worldgen")
+        .join("biome")
+        .join(format!("{}.json", biome.id));
+    written.push(project.write_json(&path, &biome.json(project))?);
+    for tag in &biome.tags {
+        let (namespace, tag) = tag.split_once(':').unwrap_or(("minecraft", tag));
+        let entry = project.resource(&biome.id);
+        written.push(project.add_to_tag(namespace, "worldgen/biome", tag, &entry)?);
+    }
+    if biome.lang {
+        let key = format!("biome.{}.{}", project.namespace(), biome.id);
+        written.push(project.add_translation(&key, &biome.name)?);
+    }
+    Ok(written)
+}
@@ -0,0 +1,249 @@
+use std::{io, path::PathBuf};
+
+use serde_json::{json, Map, Value};
+
+use crate::{
+    generator::{item, model},
+    loader::Loader,
+    project::Project,
+};
+
+/// The vanilla block model a block is built from, which decides its
+/// texture slots and the blockstate variants it needs.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum BlockTemplate {
+    CubeAll,
+    /// Pillars and logs, rotated by their `axis`.
+    CubeColumn,
+    CubeBottomTop,
+    /// Furnace-like blocks, rotated by their `facing`.
+    Orientable,
+    /// Plants and crops, drawn as two crossed planes.
+    Cross,
+    /// A different texture on each face.
+    Cube,
+}
+
+impl BlockTemplate {
+    pub const NAMES: [&'static str; 6] = [
+        "cube_all",
+        "cube_column",
+        "cube_bottom_top",
+        "orientable",
+        "cross",
+        "cube",
+    ];
+
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "cube_all" => Some(Self::CubeAll),
+            "cube_column" => Some(Self::CubeColumn),
+            "cube_bottom_top" => Some(Self::CubeBottomTop),
+            "orientable" => Some(Self::Orientable),
+            "cross" => Some(Self::Cross),
+            "cube" => Some(Self::Cube),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        Self::NAMES[*self as usize]
+    }
+
+    pub fn parent(&self) -> &'static str {
+        match self {
+            Self::CubeAll => "minecraft:block/cube_all",
+            Self::CubeColumn => "minecraft:block/cube_column",
+            Self::CubeBottomTop => "minecraft:block/cube_bottom_top",
+            Self::Orientable => "minecraft:block/orientable",
+            Self::Cross => "minecraft:block/cross",
+            Self::Cube => "minecraft:block/cube",
+        }
+    }
+
+    /// The texture slots the parent model reads.
+    pub fn slots(&self) -> &'static [&'static str] {
+        match self {
+            Self::CubeAll => &["all"],
+            Self::CubeColumn => &["end", "side"],
+            Self::CubeBottomTop => &["top", "bottom", "side"],
+            Self::Orientable => &["top", "front", "side"],
+            Self::Cross => &["cross"],
+            Self::Cube => &["north", "south", "east", "west", "up", "down"],
+        }
+    }
+
+    /// Fills every slot with `block/<id>`, or `block/<id>_<slot>` when there
+    /// is more than one, then applies the `slot=texture` pairs in `input`.
+    pub fn textures(
+        &self,
+        project: &Project,
+        id: &str,
+        input: &str,
+    ) -> Result<Map<String, Value>, String> {
+        let slots = self.slots();
+        let mut textures = Map::new();
+        for slot in slots {
+            let texture = if slots.len() == 1 {
+                format!("block/{}", id)
+            } else {
+                format!("block/{}_{}", id, slot)
+            };
+            textures.insert(slot.to_string(), json!(project.resource(&texture)));
+        }
+        for pair in input.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+            let (slot, texture) = pair
+                .split_once('=')
+                .ok_or_else(|| format!("'{}' should be written as slot=texture.", pair))?;
+            let slot = slot.trim();
+            if !slots.contains(&slot) {
+                return Err(format!(
+                    "'{}' has no {} texture, only {}.",
+                    self.name(),
+                    slot,
+                    slots.join(", ")
+                ));
+            }
+            textures.insert(slot.into(), json!(model::location(project, texture.trim())));
+        }
+        // The plain cube parent doesn't say which face breaking particles use
+        if *self == Self::Cube {
+            let particle = textures["north"].clone();
+            textures.insert("particle".into(), particle);
+        }
+        Ok(textures)
+    }
+
+    /// The blockstate variants pointing at `model`.
+    pub fn blockstate(&self, model: &str) -> Value {
+        match self {
+            Self::CubeColumn => json!({
+                "variants": {
+                    "axis=x": { "model": model, "x": 90, "y": 90 },
+                    "axis=y": { "model": model },
+                    "axis=z": { "model": model, "x": 90 },
+                }
+            }),
+            Self::Orientable => json!({
+                "variants": {
+                    "facing=east": { "model": model, "y": 90 },
+                    "facing=north": { "model": model },
+                    "facing=south": { "model": model, "y": 180 },
+                    "facing=west": { "model": model, "y": 270 },
+                }
+            }),
+            _ => json!({
+                "variants": {
+                    "": { "model": model }
+                }
+            }),
+        }
+    }
+}
+
+/// Everything needed to generate the files of a single block.
+pub struct BlockSpec {
+    pub id: String,
+    pub name: String,
+    pub template: BlockTemplate,
+    pub textures: Map<String, Value>,
+    /// Such as `cutout`, or `None` for solid blocks.
+    pub render_type: Option<String>,
+    pub lang: bool,
+    pub item: bool,
+}
+
+/// Whether the game reads the `render_type` of block models, which only
+/// Forge and NeoForge do, from 1.19.
+pub fn model_render_types(project: &Project) -> bool {
+    project.loader() != Loader::Fabric && project.version().at_least(19, 0)
+}
+
+/// Writes the client code giving the block `id` its render type, such as
+/// `cutout`, where its model can't.
+pub fn write_render_layer(project: &Project, id: &str, render_type: &str) -> io::Result<PathBuf> {
+    let field = id.to_uppercase();
+    let code = match project.loader() {
+        Loader::Fabric => {
+            let layer = match render_type {
+                "cutout" => "getCutout",
+                "cutout_mipped" => "getCutoutMipped",
+                "translucent" => "getTranslucent",
+                _ => "getSolid",
+            };
+            format!(
+                "BlockRenderLayerMap.INSTANCE.putBlock({}, RenderLayer.{}());\n",
+                field, layer
+            )
+        }
+        Loader::Forge | Loader::NeoForge => {
+            let layer = match render_type {
+                "cutout_mipped" => "cutoutMipped",
+                layer => layer,
+            };
+            format!(
+                "ItemBlockRenderTypes.setRenderLayer({}.get(), RenderType.{}());\n",
+                field, layer
+            )
+        }
+    };
+    project.write_snippet(&format!("{}_render_layer.java", id), &code)
+}
+
+/// Writes the blockstate, block model, block item model and translation,
+/// along with the render layer code where the model can't set it, returning
+/// the files that were written.
+pub fn generate(project: &Project, block: &BlockSpec) -> io::Result<Vec<PathBuf>> {
+    let mut written = Vec::new();
+    let model_id = project.resource(&format!("block/{}", block.id));
+    let mut model = Map::new();
+    model.insert("parent".into(), json!(block.template.parent()));
+    match &block.render_type {
+        Some(render_type) if model_render_types(project) => {
+            model.insert(
+                "render_type".into(),
+                json!(format!("minecraft:{}", render_type)),
+            );
+        }
+        Some(render_type) => {
+            written.push(write_render_layer(project, &block.id, render_type)?);
+        }
+        None => (),
+    }
+    model.insert("textures".into(), Value::Object(block.textures.clone()));
+    let models = project.assets().join("models");
+    let path = models.join("block").join(format!("{}.json", block.id));
+    written.push(project.write_json(&path, &Value::Object(model))?);
+    let path = project
+        .assets()
+        .join("blockstates")
+        .join(format!("{}.json", block.id));
+    written.push(project.write_json(&path, &block.template.blockstate(&model_id))?);
+    if block.item {
+        // Plants show their texture in the inventory instead of the model
+        let item_model = if block.template == BlockTemplate::Cross {
+            let model = json!({
+                "parent": "minecraft:item/generated",
+                "textures": {
+                    "layer0": block.textures["cross"],
+                }
+            });
+            let path = models.join("item").join(format!("{}.json", block.id));
+            written.push(project.write_json(&path, &model)?);
+            project.resource(&format!("item/{}", block.id))
+        } else {
+            model_id
+        };
+        if project.version().at_least(21, 4) {
+            written.push(item::write_definition(project, &block.id, &item_model)?);
+        } else if block.template != BlockTemplate::Cross {
+            let path = models.join("item").join(format!("{}.json", block.id));
+            written.push(project.write_json(&path, &json!({ "parent": item_model }))?);
+        }
+    }
+    if block.lang {
+        let key = format!("block.{}.{}", project.namespace(), block.id);
+        written.push(project.add_translation(&key, &block.name)?);
+    }
+    Ok(written)
+}
@@ -0,0 +1,155 @@
+use std::{io, path::PathBuf};
+
+use serde_json::{json, Map, Value};
+
+use crate::{
+    generator::{block, display_name, item, model},
+    project::Project,
+};
+
+/// Everything needed to generate a crop, its seeds and what it drops.
+pub struct CropSpec {
+    pub id: String,
+    /// The last `age`, when the crop is fully grown.
+    pub max_age: u32,
+    /// The seed and produce items, in the project namespace unless they
+    /// name another one, like `minecraft:wheat`.
+    pub seed: String,
+    pub produce: String,
+    pub lang: bool,
+}
+
+impl CropSpec {
+    /// The seed and produce items that belong to the project, whose models
+    /// and names have to be generated, by path.
+    fn own_items<'a>(&'a self, project: &Project) -> Vec<&'a str> {
+        let mut items: Vec<&str> = [&self.seed, &self.produce]
+            .into_iter()
+            .filter_map(|id| match id.split_once(':') {
+                Some((namespace, path)) => (namespace == project.namespace()).then_some(path),
+                None => Some(id.as_str()),
+            })
+            .collect();
+        items.dedup();
+        items
+    }
+
+    /// Only fully grown crops drop their produce and extra seeds.
+    fn mature(&self, project: &Project) -> Value {
+        json!([{
+            "condition": "minecraft:block_state_property",
+            "block": project.resource(&self.id),
+            "properties": {
+                "age": self.max_age.to_string(),
+            }
+        }])
+    }
+
+    /// The loot table of vanilla wheat: produce or a seed, then fortune
+    /// boosted seeds once grown.
+    fn loot_table(&self, project: &Project) -> Value {
+        let seed = model::location(project, &self.seed);
+        json!({
+            "type": "minecraft:block",
+            "pools": [
+                {
+                    "rolls": 1,
+                    "bonus_rolls": 0,
+                    "entries": [{
+                        "type": "minecraft:alternatives",
+                        "children": [
+                            {
+                                "type": "minecraft:item",
+                                "name": model::location(project, &self.produce),
+                                "conditions": self.mature(project),
+                            },
+                            {
+                                "type": "minecraft:item",
+                                "name": seed,
+                            }
+                        ]
+                    }]
+                },
+                {
+                    "rolls": 1,
+                    "bonus_rolls": 0,
+                    "entries": [{
+                        "type": "minecraft:item",
+                        "name": seed,
+                        "functions": [{
+                            "function": "minecraft:apply_bonus",
+                            "enchantment": "minecraft:fortune",
+                            "formula": "minecraft:binomial_with_bonus_count",
+                            "parameters": {
+                                "extra": 3,
+                                "probability": 0.5714286,
+                            }
+                        }]
+                    }],
+                    "conditions": self.mature(project),
+                }
+            ],
+            "functions": [{
+                "function": "minecraft:explosion_decay",
+            }]
+        })
+    }
+}
+
+/// Writes a model per growth stage, the blockstate choosing between them,
+/// the models of the seed and produce items the project owns, the loot table
+/// and the `minecraft:crops` tag entry, along with the render layer code
+/// where the models can't set it, returning the files that were written.
+pub fn generate(project: &Project, crop: &CropSpec) -> io::Result<Vec<PathBuf>> {
+    let mut written = Vec::new();
+    let models = project.assets().join("models").join("block");
+    let mut variants = Map::new();
+    for age in 0..=crop.max_age {
+        let stage = format!("block/{}_stage{}", crop.id, age);
+        let mut model = json!({
+            "parent": "minecraft:block/crop",
+            "textures": {
+                "crop": project.resource(&stage),
+            }
+        });
+        if block::model_render_types(project) {
+            model["render_type"] = json!("minecraft:cutout");
+        }
+        let path = models.join(format!("{}_stage{}.json", crop.id, age));
+        written.push(project.write_json(&path, &model)?);
+        variants.insert(
+            format!("age={}", age),
+            json!({ "model": project.resource(&stage) }),
+        );
+    }
+    let path = project
+        .assets()
+        .join("blockstates")
+        .join(format!("{}.json", crop.id));
+    written.push(project.write_json(&path, &json!({ "variants": variants }))?);
+    if !block::model_render_types(project) {
+        written.push(block::write_render_layer(project, &crop.id, "cutout")?);
+    }
+    // Seeds plant the crop, so the crop block needs no item of its own
+    let items = crop.own_items(project);
+    for id in &items {
+        written.extend(item::write_generated(project, id)?);
+    }
+    let path = project
+        .data_folder("loot_table")
+        .join("blocks")
+        .join(format!("{}.json", crop.id));
+    written.push(project.write_json(&path, &crop.loot_table(project))?);
+    written.push(project.add_to_tag("minecraft", "block", "crops", &project.resource(&crop.id))?);
+    if crop.lang {
+        let namespace = project.namespace();
+        let key = format!("block.{}.{}", namespace, crop.id);
+        written.push(project.add_translation(&key, &display_name(&crop.id))?);
+        for id in items {
+            let key = format!("item.{}.{}", namespace, id);
+            written.push(project.add_translation(&key, &display_name(id))?);
+        }
+        written.dedup();
+    }
+    Ok(written)
+}
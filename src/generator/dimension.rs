@@ -0,0 +1,277 @@
+use std::{io, path::PathBuf};
+
+use serde_json::{json, Map, Value};
+
+use crate::{generator::model, project::Project};
+
+/// The lowest `min_y` and highest block a dimension can have.
+const MIN_Y: i64 = -2032;
+const MAX_Y: i64 = 2031;
+
+/// How the terrain of the dimension is made.
+pub enum Generator {
+    /// Vanilla terrain with vanilla biome placement.
+    Noise { settings: String },
+    /// Vanilla terrain covered by a single biome.
+    FixedBiome { settings: String, biome: String },
+    /// Layers of blocks, bottom first, as `(block, height)`.
+    Flat {
+        biome: String,
+        layers: Vec<(String, u32)>,
+    },
+}
+
+impl Generator {
+    /// Parses flat layers written as `bedrock 1, dirt 2, grass_block 1`,
+    /// blocks without a namespace being vanilla.
+    pub fn layers(input: &str) -> Result<Vec<(String, u32)>, String> {
+        let mut layers = Vec::new();
+        for layer in input.split(',').map(str::trim).filter(|l| !l.is_empty()) {
+            let invalid = || format!("Layer '{}' should be written as 'block height'.", layer);
+            let (block, height) = layer.split_once(' ').ok_or_else(invalid)?;
+            let height = height.trim().parse().map_err(|_| invalid())?;
+            let block = if block.contains(':') {
+                block.to_string()
+            } else {
+                format!("minecraft:{}", block)
+            };
+            layers.push((block, height));
+        }
+        if layers.is_empty() {
+            return Err(String::from("A flat dimension needs at least one layer."));
+        }
+        Ok(layers)
+    }
+
+    fn json(&self) -> Value {
+        match self {
+            Self::Noise { settings } => {
+                let biome_source = match settings.as_str() {
+                    "minecraft:end" => json!({ "type": "minecraft:the_end" }),
+                    "minecraft:nether" => {
+                        json!({ "type": "minecraft:multi_noise", "preset": "minecraft:nether" })
+                    }
+                    _ => {
+                        json!({ "type": "minecraft:multi_noise", "preset": "minecraft:overworld" })
+                    }
+                };
+                json!({
+                    "type": "minecraft:noise",
+                    "settings": settings,
+                    "biome_source": biome_source,
+                })
+            }
+            Self::FixedBiome { settings, biome } => json!({
+                "type": "minecraft:noise",
+                "settings": settings,
+                "biome_source": { "type": "minecraft:fixed", "biome": biome },
+            }),
+            Self::Flat { biome, layers } => {
+                let layers: Vec<Value> = layers
+                    .iter()
+                    .map(|(block, height)| json!({ "block": block, "height": height }))
+                    .collect();
+                json!({
+                    "type": "minecraft:flat",
+                    "settings": {
+                        "biome": biome,
+                        "layers": layers,
+                        "lakes": false,
+                        "features": false,
+                        "structure_overrides": [],
+                    }
+                })
+            }
+        }
+    }
+}
+
+/// The `dimension_type` fields, named as in the JSON.
+pub struct DimensionType {
+    pub min_y: i64,
+    pub height: i64,
+    pub logical_height: i64,
+    pub ambient_light: f64,
+    pub fixed_time: Option<i64>,
+    pub has_skylight: bool,
+    pub has_ceiling: bool,
+    pub ultrawarm: bool,
+    pub natural: bool,
+    pub coordinate_scale: f64,
+    pub bed_works: bool,
+    pub respawn_anchor_works: bool,
+    pub piglin_safe: bool,
+    pub has_raids: bool,
+    pub monster_spawn_light_level: i64,
+    pub infiniburn: String,
+    /// `overworld`, `the_nether` or `the_end`, deciding the sky and fog.
+    pub effects: String,
+}
+
+impl DimensionType {
+    /// Checks the height limits the game enforces when loading the world.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.min_y % 16 != 0 || self.height % 16 != 0 {
+            return Err(String::from("min_y and height must be multiples of 16."));
+        }
+        if self.min_y < MIN_Y || self.min_y + self.height - 1 > MAX_Y {
+            return Err(format!(
+                "The dimension must stay between y {} and {}, it reaches {}.",
+                MIN_Y,
+                MAX_Y,
+                self.min_y + self.height - 1
+            ));
+        }
+        if self.logical_height > self.height {
+            return Err(String::from(
+                "The logical height can't be above the height.",
+            ));
+        }
+        Ok(())
+    }
+
+    fn json(&self, project: &Project) -> Value {
+        let mut json = Map::new();
+        if let Some(time) = self.fixed_time {
+            json.insert("fixed_time".into(), json!(time));
+        }
+        json.insert("has_skylight".into(), json!(self.has_skylight));
+        json.insert("has_ceiling".into(), json!(self.has_ceiling));
+        json.insert("ultrawarm".into(), json!(self.ultrawarm));
+        json.insert("natural".into(), json!(self.natural));
+        json.insert("coordinate_scale".into(), json!(self.coordinate_scale));
+        json.insert("bed_works".into(), json!(self.bed_works));
+        json.insert(
+            "respawn_anchor_works".into(),
+            json!(self.respawn_anchor_works),
+        );
+        json.insert("min_y".into(), json!(self.min_y));
+        json.insert("height".into(), json!(self.height));
+        json.insert("logical_height".into(), json!(self.logical_height));
+        json.insert(
+            "infiniburn".into(),
+            json!(format!(
+                "#{}",
+                model::location(project, self.infiniburn.trim_start_matches('#'))
+            )),
+        );
+        json.insert(
+            "effects".into(),
+            json!(format!("minecraft:{}", self.effects)),
+        );
+        json.insert("ambient_light".into(), json!(self.ambient_light));
+        json.insert("piglin_safe".into(), json!(self.piglin_safe));
+        json.insert("has_raids".into(), json!(self.has_raids));
+        // Monster spawning light moved into the dimension type in 1.19
+        if project.version().at_least(19, 0) {
+            json.insert(
+                "monster_spawn_light_level".into(),
+                json!(self.monster_spawn_light_level),
+            );
+            json.insert("monster_spawn_block_light_limit".into(), json!(0));
+        }
+        Value::Object(json)
+    }
+}
+
+/// Everything needed to generate a dimension and its type.
+pub struct DimensionSpec {
+    pub id: String,
+    pub kind: DimensionType,
+    pub generator: Generator,
+}
+
+/// Validates the heights, then writes the dimension type and the dimension
+/// using it, returning the files that were written.
+pub fn generate(project: &Project, dimension: &DimensionSpec) -> Result<Vec<PathBuf>, String> {
+    dimension.kind.validate()?;
+    if let Generator::Flat { layers, .. } = &dimension.generator {
+        let total: i64 = layers.iter().map(|(_, height)| *height as i64).sum();
+        if total > dimension.kind.height {
+            return Err(format!(
+                "The flat layers are {} blocks high, more than the height of {}.",
+                total, dimension.kind.height
+            ));
+        }
+    }
+    write(project, dimension).map_err(|err| err.to_string())
+}
+
+fn write(project: &Project, dimension: &DimensionSpec) -> io::Result<Vec<PathBuf>> {
+    let file = format!("{}.json", dimension.id);
+    let path = project.data().join("dimension_type").join(&file);
+    let mut written = vec![project.write_json(&path, &dimension.kind.json(project))?];
+    let json = json!({
+        "type": project.resource(&dimension.id),
+        "generator": dimension.generator.json(),
+    });
+    let path = project.data().join("dimension").join(&file);
+    written.push(project.write_json(&path, &json)?);
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kind(min_y: i64, height: i64, logical_height: i64) -> DimensionType {
+        DimensionType {
+            min_y,
+            height,
+            logical_height,
+            ambient_light: 0.0,
+            fixed_time: None,
+            has_skylight: true,
+            has_ceiling: false,
+            ultrawarm: false,
+            natural: true,
+            coordinate_scale: 1.0,
+            bed_works: true,
+            respawn_anchor_works: false,
+            piglin_safe: false,
+            has_raids: true,
+            monster_spawn_light_level: 0,
+            infiniburn: String::from("#minecraft:infiniburn_overworld"),
+            effects: String::from("overworld"),
+        }
+    }
+
+    #[test]
+    fn valid_heights() {
+        assert!(kind(-64, 384, 384).validate().is_ok());
+        assert!(kind(MIN_Y, 4064, 0).validate().is_ok());
+        assert!(kind(0, 16, 16).validate().is_ok());
+    }
+
+    #[test]
+    fn heights_must_be_multiples_of_16() {
+        assert!(kind(-60, 384, 384).validate().is_err());
+        assert!(kind(-64, 380, 380).validate().is_err());
+    }
+
+    #[test]
+    fn heights_must_stay_in_the_world_limits() {
+        assert!(kind(MIN_Y - 16, 384, 384).validate().is_err());
+        assert!(kind(0, 2048, 2048).validate().is_err());
+        assert!(kind(MIN_Y, 4080, 0).validate().is_err());
+    }
+
+    #[test]
+    fn logical_height_fits_in_the_height() {
+        assert!(kind(-64, 384, 400).validate().is_err());
+    }
+
+    #[test]
+    fn flat_layers() {
+        assert_eq!(
+            Generator::layers("bedrock 1, mymod:ruby_block 2,").unwrap(),
+            vec![
+                (String::from("minecraft:bedrock"), 1),
+                (String::from("mymod:ruby_block"), 2),
+            ]
+        );
+        assert!(Generator::layers("").is_err());
+        assert!(Generator::layers("bedrock").is_err());
+        assert!(Generator::layers("bedrock -1").is_err());
+    }
+}
@@ -0,0 +1,240 @@
+use serde_json::{json, Map, Value};
+
+use crate::{loader::Loader, version::GameVersion};
+
+/// Seconds it takes to eat a food, normally and with `fast` set.
+const EAT_SECONDS: f64 = 1.6;
+const FAST_EAT_SECONDS: f64 = 0.8;
+
+/// The vanilla effects whose `MobEffects` constant isn't their id in
+/// uppercase, since the Mojang names predate the ids.
+const MOJANG_EFFECTS: [(&str, &str); 10] = [
+    ("speed", "MOVEMENT_SPEED"),
+    ("slowness", "MOVEMENT_SLOWDOWN"),
+    ("haste", "DIG_SPEED"),
+    ("mining_fatigue", "DIG_SLOWDOWN"),
+    ("strength", "DAMAGE_BOOST"),
+    ("instant_health", "HEAL"),
+    ("instant_damage", "HARM"),
+    ("jump_boost", "JUMP"),
+    ("nausea", "CONFUSION"),
+    ("resistance", "DAMAGE_RESISTANCE"),
+];
+
+/// A status effect a food may apply when eaten.
+pub struct FoodEffect {
+    effect: String,
+    duration: u32,
+    amplifier: u32,
+    probability: f64,
+}
+
+impl FoodEffect {
+    /// Parses effects written as `id duration [amplifier] [probability]` and
+    /// separated by `;`, e.g. `regeneration 100 1 0.5; minecraft:absorption 2400`.
+    pub fn parse_list(input: &str) -> Result<Vec<Self>, String> {
+        input
+            .split(';')
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+            .map(Self::parse)
+            .collect()
+    }
+
+    fn parse(entry: &str) -> Result<Self, String> {
+        let parts: Vec<&str> = entry.split_whitespace().collect();
+        if parts.len() < 2 || parts.len() > 4 {
+            return Err(format!(
+                "Effect '{}' should be written as 'id duration [amplifier] [probability]'.",
+                entry
+            ));
+        }
+        let number_error = |field: &str| format!("Effect '{}' has an invalid {}.", entry, field);
+        let duration = parts[1].parse().map_err(|_| number_error("duration"))?;
+        let amplifier = match parts.get(2) {
+            Some(value) => value.parse().map_err(|_| number_error("amplifier"))?,
+            None => 0,
+        };
+        let probability = match parts.get(3) {
+            Some(value) => value.parse().map_err(|_| number_error("probability"))?,
+            None => 1.0,
+        };
+        if !(0.0..=1.0).contains(&probability) {
+            return Err(number_error("probability"));
+        }
+        let effect = if parts[0].contains(':') {
+            parts[0].to_string()
+        } else {
+            format!("minecraft:{}", parts[0])
+        };
+        Ok(Self {
+            effect,
+            duration,
+            amplifier,
+            probability,
+        })
+    }
+
+    fn instance(&self) -> Value {
+        json!({
+            "id": self.effect,
+            "duration": self.duration,
+            "amplifier": self.amplifier,
+        })
+    }
+
+    /// The Java expression for the effect, e.g. `MobEffects.REGENERATION`,
+    /// in Yarn names on Fabric. Effects of other mods are looked up in the
+    /// registry.
+    fn java_effect(&self, loader: Loader, version: GameVersion) -> String {
+        let Some(path) = self.effect.strip_prefix("minecraft:") else {
+            let registry = match (loader, version.at_least(19, 3)) {
+                (Loader::Fabric, true) => "Registries.STATUS_EFFECT",
+                (Loader::Fabric, false) => "Registry.STATUS_EFFECT",
+                (_, true) => "BuiltInRegistries.MOB_EFFECT",
+                (_, false) => "Registry.MOB_EFFECT",
+            };
+            let (namespace, path) = self.effect.split_once(':').unwrap_or_default();
+            let location = loader.java_location(version, namespace, path);
+            return format!("{}.get({})", registry, location);
+        };
+        // Yarn's StatusEffects follow the ids
+        if loader == Loader::Fabric {
+            return format!("StatusEffects.{}", path.to_uppercase());
+        }
+        match MOJANG_EFFECTS.iter().find(|(id, _)| *id == path) {
+            Some((_, constant)) => format!("MobEffects.{}", constant),
+            None => format!("MobEffects.{}", path.to_uppercase()),
+        }
+    }
+}
+
+/// The food values of an item, matching vanilla's `FoodProperties`.
+pub struct FoodProperties {
+    pub nutrition: u32,
+    /// Saturation relative to the nutrition, like the apple's `0.3`.
+    pub saturation_modifier: f64,
+    pub always_edible: bool,
+    pub fast: bool,
+    pub effects: Vec<FoodEffect>,
+}
+
+impl FoodProperties {
+    /// The saturation restored when eaten, which is what components store
+    /// instead of the modifier.
+    fn saturation(&self) -> f64 {
+        round(self.nutrition as f64 * self.saturation_modifier * 2.0)
+    }
+
+    /// The item components for 1.20.5 and later. From 1.21.2 the eating time
+    /// and effects live in the separate `minecraft:consumable` component.
+    pub fn components(&self, version: GameVersion) -> Map<String, Value> {
+        let seconds = if self.fast {
+            FAST_EAT_SECONDS
+        } else {
+            EAT_SECONDS
+        };
+        let mut food = Map::new();
+        food.insert("nutrition".into(), json!(self.nutrition));
+        food.insert("saturation".into(), json!(self.saturation()));
+        if self.always_edible {
+            food.insert("can_always_eat".into(), json!(true));
+        }
+        let mut components = Map::new();
+        if version.at_least(21, 2) {
+            components.insert("minecraft:food".into(), Value::Object(food));
+            let mut consumable = Map::new();
+            if self.fast {
+                consumable.insert("consume_seconds".into(), json!(seconds));
+            }
+            if !self.effects.is_empty() {
+                let effects: Vec<Value> = self
+                    .effects
+                    .iter()
+                    .map(|effect| {
+                        json!({
+                            "type": "minecraft:apply_effects",
+                            "effects": [effect.instance()],
+                            "probability": effect.probability,
+                        })
+                    })
+                    .collect();
+                consumable.insert("on_consume_effects".into(), Value::Array(effects));
+            }
+            components.insert("minecraft:consumable".into(), Value::Object(consumable));
+        } else {
+            if self.fast {
+                food.insert("eat_seconds".into(), json!(seconds));
+            }
+            if !self.effects.is_empty() {
+                let effects: Vec<Value> = self
+                    .effects
+                    .iter()
+                    .map(|effect| {
+                        json!({
+                            "effect": effect.instance(),
+                            "probability": effect.probability,
+                        })
+                    })
+                    .collect();
+                food.insert("effects".into(), Value::Array(effects));
+            }
+            components.insert("minecraft:food".into(), Value::Object(food));
+        }
+        components
+    }
+
+    /// A `FoodProperties.Builder` expression for versions before item
+    /// components, with its calls indented by `indent` spaces. Fabric gets
+    /// Yarn's `FoodComponent.Builder` instead.
+    pub fn java_builder(&self, loader: Loader, version: GameVersion, indent: usize) -> String {
+        let pad = " ".repeat(indent);
+        let yarn = loader == Loader::Fabric;
+        let mut lines = if yarn {
+            vec![
+                String::from("new FoodComponent.Builder()"),
+                format!("{}.hunger({})", pad, self.nutrition),
+                format!("{}.saturationModifier({}f)", pad, self.saturation_modifier),
+            ]
+        } else {
+            vec![
+                String::from("new FoodProperties.Builder()"),
+                format!("{}.nutrition({})", pad, self.nutrition),
+                format!("{}.saturationMod({}f)", pad, self.saturation_modifier),
+            ]
+        };
+        if self.always_edible {
+            let call = if yarn { "alwaysEdible" } else { "alwaysEat" };
+            lines.push(format!("{}.{}()", pad, call));
+        }
+        if self.fast {
+            let call = if yarn { "snack" } else { "fast" };
+            lines.push(format!("{}.{}()", pad, call));
+        }
+        // Forge and NeoForge take a supplier, so the effect can be registered
+        // after the food
+        let (call, instance) = if yarn {
+            ("statusEffect", "new StatusEffectInstance")
+        } else {
+            ("effect", "() -> new MobEffectInstance")
+        };
+        for effect in &self.effects {
+            lines.push(format!(
+                "{}.{}({}({}, {}, {}), {}f)",
+                pad,
+                call,
+                instance,
+                effect.java_effect(loader, version),
+                effect.duration,
+                effect.amplifier,
+                effect.probability
+            ));
+        }
+        lines.push(format!("{}.build()", pad));
+        lines.join("\n")
+    }
+}
+
+fn round(value: f64) -> f64 {
+    (value * 1000.0).round() / 1000.0
+}
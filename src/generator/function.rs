@@ -0,0 +1,114 @@
+use std::{io, path::PathBuf};
+
+use crate::project::Project;
+
+/// The starter contents a function can be created with.
+pub const TEMPLATES: [&str; 4] = ["empty", "scoreboard init", "tick loop", "raycast"];
+
+/// The function tags of the `minecraft` namespace the game runs by itself.
+pub const RUN_ON: [&str; 3] = ["load", "tick", "none"];
+
+/// Everything needed to create a function.
+pub struct FunctionSpec {
+    /// The path inside the function folder, such as `gameplay/init`.
+    pub path: String,
+    /// One of `TEMPLATES`.
+    pub template: String,
+    /// The scoreboard objective the templates use.
+    pub objective: String,
+    /// One of `RUN_ON`.
+    pub run_on: String,
+}
+
+impl FunctionSpec {
+    /// The files of the template as paths next to the function, `""` being
+    /// the function itself, with their contents.
+    fn files(&self, project: &Project) -> Vec<(String, String)> {
+        let id = project.resource(&self.path);
+        let objective = &self.objective;
+        match self.template.as_str() {
+            "scoreboard init" => vec![(
+                String::new(),
+                format!(
+                    "# Sets up the scoreboards, safe to run again on every reload\n\
+                     scoreboard objectives add {objective} dummy\n\
+                     scoreboard players set #loaded {objective} 1\n"
+                ),
+            )],
+            "tick loop" => vec![
+                (
+                    String::new(),
+                    format!(
+                        "# Runs 20 times a second\n\
+                         execute as @a at @s run function {id}_player\n"
+                    ),
+                ),
+                (
+                    String::from("_player"),
+                    format!(
+                        "# Runs as and at every player each tick\n\
+                         scoreboard players add @s {objective} 1\n"
+                    ),
+                ),
+            ],
+            // The ray walks forward from the eyes until it leaves the air or
+            // gets 32 blocks away from whoever cast it
+            "raycast" => vec![
+                (
+                    String::new(),
+                    format!(
+                        "# Casts a ray from the eyes of the entity running it\n\
+                         execute anchored eyes positioned ^ ^ ^ run function {id}_step\n"
+                    ),
+                ),
+                (
+                    String::from("_step"),
+                    format!(
+                        "# One step of the ray, runs at its current position\n\
+                         execute unless block ~ ~ ~ minecraft:air run function {id}_hit\n\
+                         execute if block ~ ~ ~ minecraft:air if entity @s[distance=..32] \
+                         positioned ^ ^ ^0.25 run function {id}_step\n"
+                    ),
+                ),
+                (
+                    String::from("_hit"),
+                    String::from(
+                        "# Runs where the ray hit a block\n\
+                         particle minecraft:flame ~ ~ ~ 0 0 0 0 1\n",
+                    ),
+                ),
+            ],
+            _ => vec![(String::new(), String::new())],
+        }
+    }
+}
+
+/// Writes the function and the ones its template calls, and adds it to the
+/// `load` or `tick` function tag. Existing functions are never overwritten
+/// since they are written by hand.
+pub fn generate(project: &Project, function: &FunctionSpec) -> io::Result<Vec<PathBuf>> {
+    let folder = project.data_folder("function");
+    let files: Vec<(PathBuf, String)> = function
+        .files(project)
+        .into_iter()
+        .map(|(suffix, contents)| {
+            let path = folder.join(format!("{}{}.mcfunction", function.path, suffix));
+            (path, contents)
+        })
+        .collect();
+    if let Some((path, _)) = files.iter().find(|(path, _)| path.exists()) {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("{} already exists.", path.display()),
+        ));
+    }
+    let mut written = Vec::new();
+    for (path, contents) in &files {
+        written.push(project.write(path, contents)?);
+    }
+    if function.run_on != "none" {
+        let entry = project.resource(&function.path);
+        written.push(project.add_to_tag("minecraft", "function", &function.run_on, &entry)?);
+    }
+    Ok(written)
+}
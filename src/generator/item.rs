@@ -0,0 +1,203 @@
+use std::{io, path::PathBuf};
+
+use serde_json::{json, Map, Value};
+
+use crate::{
+    generator::{food::FoodProperties, item_model, model::Override},
+    loader::Loader,
+    project::Project,
+};
+
+/// The stack size items get unless told otherwise.
+const DEFAULT_STACK_SIZE: u32 = 64;
+
+/// Everything needed to generate the files of a single item.
+pub struct ItemSpec {
+    pub id: String,
+    pub name: String,
+    /// The model parent, such as `minecraft:item/generated`.
+    pub parent: String,
+    /// Texture layers keyed as `layer0`, `layer1`...
+    pub layers: Map<String, Value>,
+    /// Display transforms keyed by context, such as `gui`.
+    pub display: Map<String, Value>,
+    pub overrides: Vec<Override>,
+    /// The model of the `items/<id>.json` definition used from 1.21.4, built
+    /// in the tree editor. Defaults to the item model.
+    pub definition: Option<Value>,
+    pub lang: bool,
+    pub stack_size: u32,
+    /// One of `common`, `uncommon`, `rare` or `epic`.
+    pub rarity: String,
+    /// Zero for items that can't be damaged.
+    pub durability: u32,
+    pub food: Option<FoodProperties>,
+}
+
+impl ItemSpec {
+    /// The item components for 1.20.5 and later, leaving out defaults.
+    fn components(&self, project: &Project) -> Map<String, Value> {
+        let mut components = Map::new();
+        if self.durability > 0 {
+            // Like Item.Properties#durability, since the game rejects items
+            // that are both damageable and stackable
+            components.insert("minecraft:max_damage".into(), json!(self.durability));
+            components.insert("minecraft:max_stack_size".into(), json!(1));
+            components.insert("minecraft:damage".into(), json!(0));
+        } else if self.stack_size != DEFAULT_STACK_SIZE {
+            components.insert("minecraft:max_stack_size".into(), json!(self.stack_size));
+        }
+        if self.rarity != "common" {
+            components.insert("minecraft:rarity".into(), json!(self.rarity));
+        }
+        if let Some(food) = &self.food {
+            components.extend(food.components(project.version()));
+        }
+        components
+    }
+
+    /// The `Item.Properties` to register the item with on versions before
+    /// item components, or `None` if the defaults are fine. Fabric gets
+    /// Yarn's `Item.Settings` instead.
+    fn java_properties(&self, project: &Project) -> Option<String> {
+        let yarn = project.loader() == Loader::Fabric;
+        let mut calls = Vec::new();
+        if self.durability > 0 {
+            let call = if yarn { "maxDamage" } else { "durability" };
+            calls.push(format!(".{}({})", call, self.durability));
+        } else if self.stack_size != DEFAULT_STACK_SIZE {
+            let call = if yarn { "maxCount" } else { "stacksTo" };
+            calls.push(format!(".{}({})", call, self.stack_size));
+        }
+        if self.rarity != "common" {
+            calls.push(format!(".rarity(Rarity.{})", self.rarity.to_uppercase()));
+        }
+        if let Some(food) = &self.food {
+            let builder = food.java_builder(project.loader(), project.version(), 16);
+            calls.push(format!(".food({})", builder));
+        }
+        if calls.is_empty() {
+            return None;
+        }
+        let settings = if yarn { "Settings" } else { "Properties" };
+        let mut lines = vec![format!("new Item.{}()", settings)];
+        lines.extend(calls.into_iter().map(|call| format!("        {}", call)));
+        Some(lines.join("\n") + "\n")
+    }
+}
+
+/// Writes the item model, its translation and the properties that have to
+/// be set when registering it, returning the files that were written.
+pub fn generate(project: &Project, item: &ItemSpec) -> io::Result<Vec<PathBuf>> {
+    let mut written = Vec::new();
+    let mut model = Map::new();
+    model.insert("parent".into(), json!(item.parent));
+    if !item.layers.is_empty() {
+        model.insert("textures".into(), Value::Object(item.layers.clone()));
+    }
+    if !item.display.is_empty() {
+        model.insert("display".into(), Value::Object(item.display.clone()));
+    }
+    if !item.overrides.is_empty() {
+        let overrides = item.overrides.iter().map(|o| o.json(project)).collect();
+        model.insert("overrides".into(), Value::Array(overrides));
+    }
+    let models = project.assets().join("models").join("item");
+    let path = models.join(format!("{}.json", item.id));
+    written.push(project.write_json(&path, &Value::Object(model))?);
+    // Like vanilla's bow_pulling_0, sub-models inherit from the item model
+    // so they keep its display transforms.
+    for entry in &item.overrides {
+        let sub_model = json!({
+            "parent": project.resource(&format!("item/{}", item.id)),
+            "textures": {
+                "layer0": entry.texture(),
+            }
+        });
+        let path = models.join(format!("{}.json", entry.model()));
+        written.push(project.write_json(&path, &sub_model)?);
+    }
+    // 1.21.4 moved the choice of model out of the model into item definitions
+    if project.version().at_least(21, 4) {
+        let own_model = project.resource(&format!("item/{}", item.id));
+        let definition = match &item.definition {
+            Some(definition) => definition.clone(),
+            None => json!({ "type": "minecraft:model", "model": own_model }),
+        };
+        let prefix = project.resource("item/");
+        for model in item_model::models(&definition) {
+            let path = match model.strip_prefix(&prefix) {
+                Some(path) if model != own_model => models.join(format!("{}.json", path)),
+                _ => continue,
+            };
+            if !path.exists() {
+                let sub_model = json!({
+                    "parent": own_model,
+                    "textures": {
+                        "layer0": model,
+                    }
+                });
+                written.push(project.write_json(&path, &sub_model)?);
+            }
+        }
+        let path = definition_path(project, &item.id);
+        written.push(project.write_json(&path, &json!({ "model": definition }))?);
+    }
+    if item.lang {
+        let key = format!("item.{}.{}", project.namespace(), item.id);
+        written.push(project.add_translation(&key, &item.name)?);
+    }
+    // Items only carry their properties as components from 1.20.5, before
+    // that they are set in code.
+    if project.version().at_least(20, 5) {
+        let components = item.components(project);
+        if !components.is_empty() {
+            let mut contents = serde_json::to_string_pretty(&Value::Object(components))?;
+            contents.push('\n');
+            let name = format!("{}_components.json", item.id);
+            written.push(project.write_snippet(&name, &contents)?);
+        }
+    } else if let Some(properties) = item.java_properties(project) {
+        let name = format!("{}_properties.java", item.id);
+        written.push(project.write_snippet(&name, &properties)?);
+    }
+    Ok(written)
+}
+
+fn definition_path(project: &Project, id: &str) -> PathBuf {
+    project.assets().join("items").join(format!("{}.json", id))
+}
+
+/// Writes the 1.21.4 `items/<id>.json` definition showing a single model.
+pub fn write_definition(project: &Project, id: &str, model: &str) -> io::Result<PathBuf> {
+    let definition = json!({
+        "model": {
+            "type": "minecraft:model",
+            "model": model,
+        }
+    });
+    project.write_json(&definition_path(project, id), &definition)
+}
+
+/// Writes a flat `item/generated` model showing `item/<id>`, plus its
+/// definition on 1.21.4 and later.
+pub fn write_generated(project: &Project, id: &str) -> io::Result<Vec<PathBuf>> {
+    let mut written = Vec::new();
+    let model_id = project.resource(&format!("item/{}", id));
+    let model = json!({
+        "parent": "minecraft:item/generated",
+        "textures": {
+            "layer0": model_id,
+        }
+    });
+    let path = project
+        .assets()
+        .join("models")
+        .join("item")
+        .join(format!("{}.json", id));
+    written.push(project.write_json(&path, &model)?);
+    if project.version().at_least(21, 4) {
+        written.push(write_definition(project, id, &model_id)?);
+    }
+    Ok(written)
+}
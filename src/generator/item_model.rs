@@ -0,0 +1,401 @@
+use serde_json::{json, Map, Value};
+
+use crate::{generator::model, project::Project};
+
+/// A row of the item model tree, nested under the closest row above it
+/// with one level less of depth.
+pub struct TreeRow {
+    pub depth: usize,
+    pub text: String,
+}
+
+/// Builds the `model` of an `items/<id>.json` definition from the rows of
+/// the tree editor, or `None` when the tree is empty.
+///
+/// Rows are written as `[key: ]kind args`, where `kind` is one of `model`,
+/// `condition`, `select`, `range_dispatch` or `empty`. Rows under a
+/// `condition` are keyed `true`/`false`, rows under `select` by the case
+/// they match and rows under `range_dispatch` by their threshold. Both of
+/// the latter take a `fallback` row.
+pub fn parse_tree(project: &Project, rows: &[TreeRow]) -> Result<Option<Value>, String> {
+    let rows: Vec<&TreeRow> = rows.iter().filter(|r| !r.text.trim().is_empty()).collect();
+    if rows.is_empty() {
+        return Ok(None);
+    }
+    let mut idx = 0;
+    let (key, node) = parse_node(project, &rows, &mut idx)?;
+    if let Some(key) = key {
+        return Err(format!("The top row can't have a '{}:' key.", key));
+    }
+    if idx < rows.len() {
+        return Err(format!(
+            "Row '{}' is outside of the top node.",
+            rows[idx].text.trim()
+        ));
+    }
+    Ok(Some(node))
+}
+
+/// The models a tree points to, so the ones missing can be generated.
+pub fn models(node: &Value) -> Vec<String> {
+    let mut models = Vec::new();
+    let mut pending = vec![node];
+    while let Some(node) = pending.pop() {
+        match node {
+            Value::Object(map) => {
+                if map.get("type") == Some(&json!("minecraft:model")) {
+                    if let Some(Value::String(model)) = map.get("model") {
+                        models.push(model.clone());
+                    }
+                }
+                pending.extend(map.values());
+            }
+            Value::Array(values) => pending.extend(values),
+            _ => (),
+        }
+    }
+    models
+}
+
+fn parse_node(
+    project: &Project,
+    rows: &[&TreeRow],
+    idx: &mut usize,
+) -> Result<(Option<String>, Value), String> {
+    let row = rows[*idx];
+    let depth = row.depth;
+    *idx += 1;
+    let text = row.text.trim();
+    let (key, text) = match text.split_once(": ") {
+        Some((key, rest)) => (Some(key.trim().to_string()), rest.trim()),
+        None => (None, text),
+    };
+    let mut words = text.split_whitespace();
+    let kind = words.next().unwrap_or_default();
+    let args: Vec<&str> = words.collect();
+    // Rows one level deeper belong to this node
+    let mut children = Vec::new();
+    while *idx < rows.len() && rows[*idx].depth > depth {
+        if rows[*idx].depth != depth + 1 {
+            return Err(format!(
+                "Row '{}' is nested too deep.",
+                rows[*idx].text.trim()
+            ));
+        }
+        children.push(parse_node(project, rows, idx)?);
+    }
+    let leaf = |children: &Vec<(Option<String>, Value)>| {
+        if children.is_empty() {
+            Ok(())
+        } else {
+            Err(format!("'{}' can't have rows under it.", kind))
+        }
+    };
+    let mut node = Map::new();
+    match kind {
+        "model" => {
+            leaf(&children)?;
+            let (model, tints) = match args.split_first() {
+                Some((model, tints)) => (model, tints),
+                None => return Err(String::from("'model' needs the model to use.")),
+            };
+            node.insert("type".into(), json!("minecraft:model"));
+            node.insert("model".into(), json!(model::location(project, model)));
+            if !tints.is_empty() {
+                let tints: Result<Vec<Value>, String> = tints.iter().map(|t| tint(t)).collect();
+                node.insert("tints".into(), Value::Array(tints?));
+            }
+        }
+        "empty" => {
+            leaf(&children)?;
+            node.insert("type".into(), json!("minecraft:empty"));
+        }
+        "condition" => {
+            property(&mut node, kind, &args)?;
+            let mut on_true = None;
+            let mut on_false = None;
+            for (key, child) in children {
+                match key.as_deref() {
+                    Some("true") => on_true = Some(child),
+                    Some("false") => on_false = Some(child),
+                    _ => {
+                        return Err(String::from(
+                            "Rows under 'condition' need a true: or false: key.",
+                        ))
+                    }
+                }
+            }
+            match (on_true, on_false) {
+                (Some(on_true), Some(on_false)) => {
+                    node.insert("on_true".into(), on_true);
+                    node.insert("on_false".into(), on_false);
+                }
+                _ => {
+                    return Err(String::from(
+                        "'condition' needs both a true: and a false: row.",
+                    ))
+                }
+            }
+        }
+        "select" | "range_dispatch" => {
+            property(&mut node, kind, &args)?;
+            let mut entries = Vec::new();
+            for (key, child) in children {
+                match key.as_deref() {
+                    Some("fallback") => {
+                        node.insert("fallback".into(), child);
+                    }
+                    Some(key) if kind == "select" => {
+                        entries.push(json!({ "when": key, "model": child }));
+                    }
+                    Some(key) => {
+                        let threshold: f64 = key
+                            .parse()
+                            .map_err(|_| format!("'{}' is not a valid threshold.", key))?;
+                        entries.push(json!({ "threshold": threshold, "model": child }));
+                    }
+                    None => return Err(format!("Rows under '{}' need a key.", kind)),
+                }
+            }
+            let field = if kind == "select" { "cases" } else { "entries" };
+            node.insert(field.into(), Value::Array(entries));
+        }
+        _ => {
+            return Err(format!(
+                "'{}' is not one of model, condition, select, range_dispatch or empty.",
+                kind
+            ))
+        }
+    }
+    Ok((key, Value::Object(node)))
+}
+
+/// Sets the `type` and `property` of a branch node, followed by the extra
+/// `name=value` fields some properties need.
+fn property(node: &mut Map<String, Value>, kind: &str, args: &[&str]) -> Result<(), String> {
+    let (property, fields) = match args.split_first() {
+        Some(split) => split,
+        None => return Err(format!("'{}' needs a property.", kind)),
+    };
+    node.insert("type".into(), json!(format!("minecraft:{}", kind)));
+    node.insert("property".into(), json!(namespaced(property)));
+    for field in fields {
+        let (name, value) = field
+            .split_once('=')
+            .ok_or_else(|| format!("'{}' should be written as name=value.", field))?;
+        node.insert(name.to_string(), scalar(value));
+    }
+    Ok(())
+}
+
+/// Parses tints written as `type[:color]`, such as `dye:ff0000` or
+/// `minecraft:dye:#ff0000`. What follows the last `:` is a color when it
+/// starts with `#` or is 6 or 8 hex digits, so namespaced types like
+/// `mymod:face` keep their namespace.
+fn tint(input: &str) -> Result<Value, String> {
+    let (kind, color) = match input.rsplit_once(':') {
+        Some((kind, color)) if color.starts_with('#') || is_hex_color(color) => {
+            let color = i64::from_str_radix(color.trim_start_matches('#'), 16)
+                .map_err(|_| format!("'{}' is not a hex color.", color))?;
+            (namespaced(kind), Some(color))
+        }
+        Some((kind, color)) if kind.contains(':') => {
+            return Err(format!("'{}' is not a hex color.", color));
+        }
+        _ => (namespaced(input), None),
+    };
+    let field = match kind.as_str() {
+        "minecraft:constant" => "value",
+        _ => "default",
+    };
+    let mut tint = Map::new();
+    tint.insert("type".into(), json!(kind));
+    if let Some(color) = color {
+        tint.insert(field.into(), json!(color));
+    }
+    Ok(Value::Object(tint))
+}
+
+/// Whether `color` is written as `rrggbb` or `aarrggbb`.
+fn is_hex_color(color: &str) -> bool {
+    matches!(color.len(), 6 | 8) && color.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+fn namespaced(id: &str) -> String {
+    if id.contains(':') {
+        id.to_string()
+    } else {
+        format!("minecraft:{}", id)
+    }
+}
+
+/// Reads `true`, `false` and numbers as such, anything else as a string.
+fn scalar(value: &str) -> Value {
+    if let Ok(flag) = value.parse::<bool>() {
+        json!(flag)
+    } else if let Ok(number) = value.parse::<i64>() {
+        json!(number)
+    } else if let Ok(number) = value.parse::<f64>() {
+        json!(number)
+    } else {
+        json!(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::*;
+    use crate::{loader::Loader, version::GameVersion};
+
+    fn project() -> Project {
+        Project::new(
+            Path::new("ruby_mod"),
+            "ruby",
+            GameVersion::new(21, 4),
+            Loader::Forge,
+        )
+    }
+
+    fn rows(rows: &[(usize, &str)]) -> Vec<TreeRow> {
+        rows.iter()
+            .map(|(depth, text)| TreeRow {
+                depth: *depth,
+                text: text.to_string(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn empty_tree() {
+        let tree = rows(&[(0, ""), (0, "  ")]);
+        assert_eq!(parse_tree(&project(), &tree).unwrap(), None);
+    }
+
+    #[test]
+    fn model_with_tints() {
+        let tree = rows(&[(0, "model item/ruby dye:ff0000 constant:#00ff00")]);
+        assert_eq!(
+            parse_tree(&project(), &tree).unwrap(),
+            Some(json!({
+                "type": "minecraft:model",
+                "model": "ruby:item/ruby",
+                "tints": [
+                    { "type": "minecraft:dye", "default": 0xff0000 },
+                    { "type": "minecraft:constant", "value": 0x00ff00 },
+                ],
+            }))
+        );
+    }
+
+    #[test]
+    fn condition_tree() {
+        let tree = rows(&[
+            (0, "condition using_item"),
+            (1, "true: model item/ruby_pulling"),
+            (1, "false: model minecraft:item/stick"),
+        ]);
+        assert_eq!(
+            parse_tree(&project(), &tree).unwrap(),
+            Some(json!({
+                "type": "minecraft:condition",
+                "property": "minecraft:using_item",
+                "on_true": { "type": "minecraft:model", "model": "ruby:item/ruby_pulling" },
+                "on_false": { "type": "minecraft:model", "model": "minecraft:item/stick" },
+            }))
+        );
+    }
+
+    #[test]
+    fn select_and_range_dispatch() {
+        let tree = rows(&[
+            (0, "select display_context"),
+            (1, "gui: model item/ruby_gui"),
+            (1, "fallback: range_dispatch use_duration scale=0.05"),
+            (2, "0.5: model item/ruby_half"),
+            (2, "fallback: empty"),
+        ]);
+        assert_eq!(
+            parse_tree(&project(), &tree).unwrap(),
+            Some(json!({
+                "type": "minecraft:select",
+                "property": "minecraft:display_context",
+                "fallback": {
+                    "type": "minecraft:range_dispatch",
+                    "property": "minecraft:use_duration",
+                    "scale": 0.05,
+                    "fallback": { "type": "minecraft:empty" },
+                    "entries": [{
+                        "threshold": 0.5,
+                        "model": { "type": "minecraft:model", "model": "ruby:item/ruby_half" },
+                    }],
+                },
+                "cases": [{
+                    "when": "gui",
+                    "model": { "type": "minecraft:model", "model": "ruby:item/ruby_gui" },
+                }],
+            }))
+        );
+    }
+
+    #[test]
+    fn invalid_trees() {
+        let invalid = [
+            vec![(0, "true: model item/ruby")],
+            vec![(0, "model item/ruby"), (0, "model item/other")],
+            vec![(0, "condition using_item"), (2, "true: empty")],
+            vec![(0, "model item/ruby"), (1, "empty")],
+            vec![(0, "condition using_item"), (1, "true: empty")],
+            vec![
+                (0, "condition using_item"),
+                (1, "empty"),
+                (1, "false: empty"),
+            ],
+            vec![(0, "range_dispatch count"), (1, "high: empty")],
+            vec![(0, "select")],
+            vec![(0, "select display_context scale")],
+            vec![(0, "model")],
+            vec![(0, "composite")],
+        ];
+        for tree in invalid {
+            assert!(parse_tree(&project(), &rows(&tree)).is_err(), "{:?}", tree);
+        }
+    }
+
+    #[test]
+    fn tint_without_color() {
+        assert_eq!(
+            tint("minecraft:constant").unwrap(),
+            json!({ "type": "minecraft:constant" })
+        );
+        assert_eq!(tint("dye").unwrap(), json!({ "type": "minecraft:dye" }));
+    }
+
+    #[test]
+    fn tint_with_color() {
+        assert_eq!(
+            tint("constant:#ff0000").unwrap(),
+            json!({ "type": "minecraft:constant", "value": 0xff0000 })
+        );
+        assert_eq!(
+            tint("minecraft:dye:#ff0000").unwrap(),
+            json!({ "type": "minecraft:dye", "default": 0xff0000 })
+        );
+        assert_eq!(
+            tint("dye:ff0000").unwrap(),
+            json!({ "type": "minecraft:dye", "default": 0xff0000 })
+        );
+    }
+
+    #[test]
+    fn tint_namespaced_type_with_hex_path() {
+        assert_eq!(tint("mymod:face").unwrap(), json!({ "type": "mymod:face" }));
+    }
+
+    #[test]
+    fn tint_invalid_color() {
+        assert!(tint("dye:#red").is_err());
+        assert!(tint("minecraft:dye:red").is_err());
+    }
+}
@@ -0,0 +1,31 @@
+pub mod advancement;
+pub mod biome;
+pub mod block;
+pub mod crop;
+pub mod dimension;
+pub mod food;
+pub mod function;
+pub mod item;
+pub mod item_model;
+pub mod model;
+pub mod ore;
+pub mod pack;
+pub mod particle;
+pub mod recipe;
+pub mod sound;
+pub mod worldgen;
+
+/// Turns an id such as `ruby_apple` into `Ruby Apple`.
+pub fn display_name(id: &str) -> String {
+    id.split('_')
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        })
+        .collect::<Vec<String>>()
+        .join(" ")
+}
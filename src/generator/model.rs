@@ -0,0 +1,138 @@
+use serde_json::{json, Map, Value};
+
+use crate::project::{self, Project};
+
+/// The vanilla parents offered before the models found in the project.
+pub const VANILLA_PARENTS: [&str; 3] = [
+    "minecraft:item/generated",
+    "minecraft:item/handheld",
+    "minecraft:item/handheld_rod",
+];
+
+/// Every parent an item model can use: the vanilla ones followed by the
+/// item and block models already in the project.
+pub fn parents(project: &Project) -> Vec<String> {
+    let mut parents: Vec<String> = VANILLA_PARENTS.iter().map(|p| p.to_string()).collect();
+    for folder in ["item", "block"] {
+        let dir = project.assets().join("models").join(folder);
+        for path in project::list_files(&dir, "json") {
+            if let Ok(model) = path.with_extension("").strip_prefix(&dir) {
+                let model = model.to_string_lossy().replace('\\', "/");
+                parents.push(project.resource(&format!("{}/{}", folder, model)));
+            }
+        }
+    }
+    parents
+}
+
+/// Turns a texture or model path into a resource location, using the
+/// project namespace when the path has none.
+pub fn location(project: &Project, path: &str) -> String {
+    if path.contains(':') {
+        path.to_string()
+    } else {
+        project.resource(path)
+    }
+}
+
+/// Writes whole numbers without a fraction, the way vanilla models do.
+fn number(value: f64) -> Value {
+    if value.fract() == 0.0 && value.abs() < i64::MAX as f64 {
+        json!(value as i64)
+    } else {
+        json!(value)
+    }
+}
+
+/// Parses comma separated textures into `layer0`, `layer1`... entries.
+pub fn layers(project: &Project, input: &str) -> Map<String, Value> {
+    input
+        .split(',')
+        .map(str::trim)
+        .filter(|texture| !texture.is_empty())
+        .enumerate()
+        .map(|(idx, texture)| (format!("layer{}", idx), json!(location(project, texture))))
+        .collect()
+}
+
+/// Parses a display transform written as `rotation x y z; translation x y z;
+/// scale x y z`, where every part is optional. Returns `None` when empty.
+pub fn transform(input: &str) -> Result<Option<Value>, String> {
+    let mut transform = Map::new();
+    for part in input.split(';').map(str::trim).filter(|p| !p.is_empty()) {
+        let words: Vec<&str> = part.split_whitespace().collect();
+        let invalid = || {
+            format!(
+                "Transform '{}' should be written as 'rotation|translation|scale x y z'.",
+                part
+            )
+        };
+        if words.len() != 4 || !["rotation", "translation", "scale"].contains(&words[0]) {
+            return Err(invalid());
+        }
+        let mut values = Vec::new();
+        for word in &words[1..] {
+            values.push(number(word.parse().map_err(|_| invalid())?));
+        }
+        transform.insert(words[0].to_string(), Value::Array(values));
+    }
+    if transform.is_empty() {
+        Ok(None)
+    } else {
+        Ok(Some(Value::Object(transform)))
+    }
+}
+
+/// An `overrides` entry swapping the item model when its predicates match.
+pub struct Override {
+    predicate: Map<String, Value>,
+    /// Path of the sub-model inside `models/item`.
+    model: String,
+    texture: String,
+}
+
+impl Override {
+    /// Parses rows written as `pull=0.65 pulling=1 -> model [texture]`. The
+    /// texture defaults to `item/<model>`.
+    pub fn parse(project: &Project, row: &str) -> Result<Self, String> {
+        let invalid = || {
+            format!(
+                "Override '{}' should be written as 'predicate=value ... -> model [texture]'.",
+                row
+            )
+        };
+        let (predicates, target) = row.split_once("->").ok_or_else(invalid)?;
+        let mut predicate = Map::new();
+        for entry in predicates.split([' ', ',']).filter(|e| !e.is_empty()) {
+            let (name, value) = entry.split_once('=').ok_or_else(invalid)?;
+            let value: f64 = value.parse().map_err(|_| invalid())?;
+            predicate.insert(name.to_string(), number(value));
+        }
+        let target: Vec<&str> = target.split_whitespace().collect();
+        let (model, texture) = match target.as_slice() {
+            [model] => (*model, format!("item/{}", model)),
+            [model, texture] => (*model, texture.to_string()),
+            _ => return Err(invalid()),
+        };
+        if predicate.is_empty() {
+            return Err(invalid());
+        }
+        Ok(Self {
+            predicate,
+            model: model.to_string(),
+            texture: location(project, &texture),
+        })
+    }
+    pub fn model(&self) -> &str {
+        &self.model
+    }
+    pub fn texture(&self) -> &str {
+        &self.texture
+    }
+    pub fn json(&self, project: &Project) -> Value {
+        json!({
+            "predicate": self.predicate,
+            "model": project.resource(&format!("item/{}", self.model)),
+        })
+    }
+}
@@ -0,0 +1,300 @@
+use std::{io, path::PathBuf};
+
+use serde_json::{json, Value};
+
+use crate::{
+    generator::{
+        block::{self, BlockSpec, BlockTemplate},
+        display_name, item, model, recipe, worldgen,
+    },
+    project::Project,
+};
+
+/// Everything needed to generate an ore, its drops and its world generation.
+pub struct OreSpec {
+    /// The name the other ids are built from, `ruby` giving `ruby_ore`,
+    /// `deepslate_ruby_ore`, `raw_ruby` and `raw_ruby_block`.
+    pub material: String,
+    /// The item the ore and raw item smelt into.
+    pub smelts_into: String,
+    pub experience: f64,
+    pub vein_size: u32,
+    /// Veins tried per chunk.
+    pub count: u32,
+    pub min_height: i32,
+    pub max_height: i32,
+    /// `uniform`, or `trapezoid` to make veins most common halfway.
+    pub distribution: String,
+    /// The tool tier needed to mine it, such as `iron`.
+    pub tool: String,
+    pub lang: bool,
+    /// Whether the recipes get unlock advancements for the recipe book.
+    pub unlocks: bool,
+}
+
+impl OreSpec {
+    fn ore(&self) -> String {
+        format!("{}_ore", self.material)
+    }
+    fn deepslate_ore(&self) -> String {
+        format!("deepslate_{}_ore", self.material)
+    }
+    fn raw(&self) -> String {
+        format!("raw_{}", self.material)
+    }
+    fn raw_block(&self) -> String {
+        format!("raw_{}_block", self.material)
+    }
+
+    fn configured_feature(&self, project: &Project) -> Value {
+        let target = |block: &str, replaceables: &str| {
+            json!({
+                "state": { "Name": project.resource(block) },
+                "target": {
+                    "predicate_type": "minecraft:tag_match",
+                    "tag": format!("minecraft:{}_ore_replaceables", replaceables),
+                }
+            })
+        };
+        json!({
+            "type": "minecraft:ore",
+            "config": {
+                "discard_chance_on_air_exposure": 0,
+                "size": self.vein_size,
+                "targets": [
+                    target(&self.ore(), "stone"),
+                    target(&self.deepslate_ore(), "deepslate"),
+                ]
+            }
+        })
+    }
+
+    fn placed_feature(&self, project: &Project) -> Value {
+        json!({
+            "feature": project.resource(&self.ore()),
+            "placement": [
+                { "type": "minecraft:count", "count": self.count },
+                { "type": "minecraft:in_square" },
+                {
+                    "type": "minecraft:height_range",
+                    "height": {
+                        "type": format!("minecraft:{}", self.distribution),
+                        "min_inclusive": { "absolute": self.min_height },
+                        "max_inclusive": { "absolute": self.max_height },
+                    }
+                },
+                { "type": "minecraft:biome" }
+            ]
+        })
+    }
+}
+
+/// Drops the raw item, more with fortune, or the ore itself with silk touch.
+fn ore_loot_table(project: &Project, ore: &str, raw: &str) -> Value {
+    json!({
+        "type": "minecraft:block",
+        "pools": [{
+            "rolls": 1,
+            "bonus_rolls": 0,
+            "entries": [{
+                "type": "minecraft:alternatives",
+                "children": [
+                    {
+                        "type": "minecraft:item",
+                        "name": project.resource(ore),
+                        "conditions": [silk_touch(project)],
+                    },
+                    {
+                        "type": "minecraft:item",
+                        "name": project.resource(raw),
+                        "functions": [
+                            {
+                                "function": "minecraft:apply_bonus",
+                                "enchantment": "minecraft:fortune",
+                                "formula": "minecraft:ore_drops",
+                            },
+                            { "function": "minecraft:explosion_decay" }
+                        ]
+                    }
+                ]
+            }]
+        }]
+    })
+}
+
+/// Item predicates moved enchantments under `predicates` in 1.20.5.
+fn silk_touch(project: &Project) -> Value {
+    if project.version().at_least(20, 5) {
+        json!({
+            "condition": "minecraft:match_tool",
+            "predicate": {
+                "predicates": {
+                    "minecraft:enchantments": [{
+                        "enchantments": "minecraft:silk_touch",
+                        "levels": { "min": 1 },
+                    }]
+                }
+            }
+        })
+    } else {
+        json!({
+            "condition": "minecraft:match_tool",
+            "predicate": {
+                "enchantments": [{
+                    "enchantment": "minecraft:silk_touch",
+                    "levels": { "min": 1 },
+                }]
+            }
+        })
+    }
+}
+
+fn self_loot_table(project: &Project, block: &str) -> Value {
+    json!({
+        "type": "minecraft:block",
+        "pools": [{
+            "rolls": 1,
+            "bonus_rolls": 0,
+            "entries": [{ "type": "minecraft:item", "name": project.resource(block) }],
+            "conditions": [{ "condition": "minecraft:survives_explosion" }],
+        }]
+    })
+}
+
+/// The Fabric code adding the placed feature to overworld biomes, since
+/// Fabric has no biome modifier files.
+fn fabric_biome_modification(project: &Project, feature: &str) -> String {
+    let location = project
+        .loader()
+        .java_location(project.version(), project.namespace(), feature);
+    format!(
+        "BiomeModifications.addFeature(\n        \
+         BiomeSelectors.foundInOverworld(),\n        \
+         GenerationStep.Feature.UNDERGROUND_ORES,\n        \
+         RegistryKey.of(RegistryKeys.PLACED_FEATURE, {}));\n",
+        location
+    )
+}
+
+/// Writes the ore blocks, raw item and block, their recipes and loot tables,
+/// the features placing the ore and the loader specific file adding it to
+/// the overworld, returning the files that were written.
+pub fn generate(project: &Project, ore: &OreSpec) -> io::Result<Vec<PathBuf>> {
+    let mut written = Vec::new();
+    let (ore_id, deepslate_id) = (ore.ore(), ore.deepslate_ore());
+    let (raw_id, raw_block_id) = (ore.raw(), ore.raw_block());
+    let raw_block_name = format!("Block of {}", display_name(&raw_id));
+    for (id, name) in [
+        (&ore_id, display_name(&ore_id)),
+        (&deepslate_id, display_name(&deepslate_id)),
+        (&raw_block_id, raw_block_name),
+    ] {
+        let template = BlockTemplate::CubeAll;
+        let block = BlockSpec {
+            id: id.clone(),
+            name,
+            template,
+            textures: template
+                .textures(project, id, "")
+                .map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err))?,
+            render_type: None,
+            lang: ore.lang,
+            item: true,
+        };
+        written.extend(block::generate(project, &block)?);
+        for tag in ["mineable/pickaxe", &format!("needs_{}_tool", ore.tool)] {
+            written.push(project.add_to_tag("minecraft", "block", tag, &project.resource(id))?);
+        }
+    }
+    written.extend(item::write_generated(project, &raw_id)?);
+    if ore.lang {
+        let key = format!("item.{}.{}", project.namespace(), raw_id);
+        written.push(project.add_translation(&key, &display_name(&raw_id))?);
+    }
+    // Give the smelting result a model too when it's a new item of the mod
+    let output = model::location(project, &ore.smelts_into);
+    if let Some(id) = output.strip_prefix(&project.resource("")) {
+        let path = project
+            .assets()
+            .join("models")
+            .join("item")
+            .join(format!("{}.json", id));
+        if !path.exists() {
+            written.extend(item::write_generated(project, id)?);
+            if ore.lang {
+                let key = format!("item.{}.{}", project.namespace(), id);
+                written.push(project.add_translation(&key, &display_name(id))?);
+            }
+        }
+    }
+
+    let version = project.version();
+    let output_name = output.rsplit(':').next().unwrap_or_default();
+    for input in [&raw_id, &ore_id, &deepslate_id] {
+        for kind in ["smelting", "blasting"] {
+            let name = format!("{}_from_{}_{}", output_name, kind, input);
+            let recipe = recipe::cooking(
+                version,
+                kind,
+                &project.resource(input),
+                &output,
+                ore.experience,
+            );
+            written.extend(recipe::write(project, &name, &recipe, ore.unlocks)?);
+        }
+    }
+    let (raw, raw_block) = (project.resource(&raw_id), project.resource(&raw_block_id));
+    let pattern = ["###", "###", "###"];
+    let recipe = recipe::shaped(version, &pattern, &[('#', &raw)], &raw_block, 1);
+    written.extend(recipe::write(project, &raw_block_id, &recipe, ore.unlocks)?);
+    let recipe = recipe::shapeless(version, &[&raw_block], &raw, 9);
+    written.extend(recipe::write(project, &raw_id, &recipe, ore.unlocks)?);
+
+    let loot_tables = project.data_folder("loot_table").join("blocks");
+    for id in [&ore_id, &deepslate_id] {
+        let path = loot_tables.join(format!("{}.json", id));
+        written.push(project.write_json(&path, &ore_loot_table(project, id, &raw_id))?);
+    }
+    let path = loot_tables.join(format!("{}.json", raw_block_id));
+    written.push(project.write_json(&path, &self_loot_table(project, &raw_block_id))?);
+
+    let configured = ore.configured_feature(project);
+    written.push(worldgen::write(
+        project,
+        "configured_feature",
+        &ore_id,
+        &configured,
+    )?);
+    let placed = ore.placed_feature(project);
+    written.push(worldgen::write(
+        project,
+        "placed_feature",
+        &ore_id,
+        &placed,
+    )?);
+    let file = format!("{}.json", ore_id);
+    match project.loader().data_namespace(version) {
+        Some(namespace) => {
+            let modifier = json!({
+                "type": format!("{}:add_features", namespace),
+                "biomes": "#minecraft:is_overworld",
+                "features": project.resource(&ore_id),
+                "step": "underground_ores",
+            });
+            let path = project
+                .data()
+                .join(namespace)
+                .join("biome_modifier")
+                .join(&file);
+            written.push(project.write_json(&path, &modifier)?);
+        }
+        None => {
+            let name = format!("{}_biome_modification.java", ore_id);
+            let code = fabric_biome_modification(project, &ore_id);
+            written.push(project.write_snippet(&name, &code)?);
+        }
+    }
+    written.sort();
+    written.dedup();
+    Ok(written)
+}
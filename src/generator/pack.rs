@@ -0,0 +1,156 @@
+use std::{fs, io, path::PathBuf};
+
+use serde_json::{json, Map};
+
+use crate::{project::Project, version::GameVersion};
+
+/// The first release of each data pack format, newest last.
+const DATA_FORMATS: [(u32, u32, u32); 18] = [
+    (13, 0, 4),
+    (15, 0, 5),
+    (16, 2, 6),
+    (17, 0, 7),
+    (18, 0, 8),
+    (18, 2, 9),
+    (19, 0, 10),
+    (19, 4, 12),
+    (20, 0, 15),
+    (20, 2, 18),
+    (20, 3, 26),
+    (20, 5, 41),
+    (21, 0, 48),
+    (21, 2, 57),
+    (21, 4, 61),
+    (21, 5, 71),
+    (21, 6, 80),
+    (21, 7, 81),
+];
+
+/// The first release of each resource pack format, newest last.
+const RESOURCE_FORMATS: [(u32, u32, u32); 21] = [
+    (6, 1, 1),
+    (9, 0, 2),
+    (11, 0, 3),
+    (13, 0, 4),
+    (15, 0, 5),
+    (16, 2, 6),
+    (17, 0, 7),
+    (18, 0, 8),
+    (19, 0, 9),
+    (19, 3, 12),
+    (19, 4, 13),
+    (20, 0, 15),
+    (20, 2, 18),
+    (20, 3, 22),
+    (20, 5, 32),
+    (21, 0, 34),
+    (21, 2, 42),
+    (21, 4, 46),
+    (21, 5, 55),
+    (21, 6, 63),
+    (21, 7, 64),
+];
+
+/// A grey 16x16 `pack.png` to replace with the real icon.
+const PLACEHOLDER_ICON: [u8; 77] = [
+    0x89, 0x50, 0x4e, 0x47, 0x0d, 0x0a, 0x1a, 0x0a, 0x00, 0x00, 0x00, 0x0d, 0x49, 0x48, 0x44, 0x52,
+    0x00, 0x00, 0x00, 0x10, 0x00, 0x00, 0x00, 0x10, 0x08, 0x02, 0x00, 0x00, 0x00, 0x90, 0x91, 0x68,
+    0x36, 0x00, 0x00, 0x00, 0x14, 0x49, 0x44, 0x41, 0x54, 0x78, 0xda, 0x63, 0x88, 0x22, 0x11, 0x30,
+    0x8c, 0x6a, 0x18, 0xd5, 0x30, 0x7c, 0x35, 0x00, 0x00, 0x8b, 0x43, 0x0e, 0x10, 0x6e, 0xf2, 0x13,
+    0x7c, 0x00, 0x00, 0x00, 0x00, 0x49, 0x45, 0x4e, 0x44, 0xae, 0x42, 0x60, 0x82,
+];
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PackKind {
+    Data,
+    Resource,
+}
+
+impl PackKind {
+    /// The `pack_format` of the pack for `version`, or `None` before the
+    /// kind of pack existed. Versions newer than the table get its newest
+    /// format.
+    pub fn format(&self, version: GameVersion) -> Option<u32> {
+        let formats: &[(u32, u32, u32)] = match self {
+            Self::Data => &DATA_FORMATS,
+            Self::Resource => &RESOURCE_FORMATS,
+        };
+        formats
+            .iter()
+            .rev()
+            .find(|(minor, patch, _)| version.at_least(*minor, *patch))
+            .map(|(_, _, format)| *format)
+    }
+
+    /// The folders of the namespace created up front, so the pack shows
+    /// where things go.
+    fn folders(&self, project: &Project) -> Vec<PathBuf> {
+        match self {
+            Self::Data => ["advancement", "function", "loot_table", "recipe", "tags"]
+                .iter()
+                .map(|kind| project.data_folder(kind))
+                .collect(),
+            Self::Resource => ["blockstates", "lang", "models", "textures"]
+                .iter()
+                .map(|folder| project.assets().join(folder))
+                .collect(),
+        }
+    }
+}
+
+/// Everything needed to create a pack.
+pub struct PackSpec {
+    pub kind: PackKind,
+    pub description: String,
+    /// The newest version the pack claims to work on, written as its
+    /// `supported_formats`.
+    pub supported_up_to: Option<GameVersion>,
+    pub icon: bool,
+}
+
+/// Creates the skeleton of the pack at the root of `project` with its
+/// `pack.mcmeta`, returning the files that were written.
+pub fn generate(project: &Project, pack: &PackSpec) -> Result<Vec<PathBuf>, String> {
+    let version = project.version();
+    let name = match pack.kind {
+        PackKind::Data => "Data packs",
+        PackKind::Resource => "Resource packs",
+    };
+    let format = pack
+        .kind
+        .format(version)
+        .ok_or_else(|| format!("{} don't exist in {}.", name, version))?;
+    let mut meta = Map::new();
+    meta.insert("pack_format".into(), json!(format));
+    meta.insert("description".into(), json!(pack.description));
+    if let Some(max_version) = pack.supported_up_to {
+        if max_version < version {
+            return Err(format!(
+                "The pack can't support {} when it targets {}.",
+                max_version, version
+            ));
+        }
+        let max_format = pack.kind.format(max_version).unwrap_or(format);
+        meta.insert(
+            "supported_formats".into(),
+            json!({ "min_inclusive": format, "max_inclusive": max_format }),
+        );
+    }
+    let mut written = Vec::new();
+    let to_string = |err: io::Error| err.to_string();
+    for folder in pack.kind.folders(project) {
+        fs::create_dir_all(folder).map_err(to_string)?;
+    }
+    let path = project.root().join("pack.mcmeta");
+    let mcmeta = json!({ "pack": meta });
+    written.push(project.write_json(&path, &mcmeta).map_err(to_string)?);
+    let icon = project.root().join("pack.png");
+    if pack.icon && !icon.exists() {
+        written.push(
+            project
+                .write_bytes(&icon, &PLACEHOLDER_ICON)
+                .map_err(to_string)?,
+        );
+    }
+    Ok(written)
+}
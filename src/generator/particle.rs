@@ -0,0 +1,98 @@
+use std::{fs, io, path::PathBuf};
+
+use serde_json::json;
+
+use crate::{loader::Loader, project::Project};
+
+/// Everything needed to generate a particle.
+pub struct ParticleSpec {
+    pub id: String,
+    /// Whether the particle shows even with particles set to minimal.
+    pub always_show: bool,
+    /// Whether to write the Java registration of the particle type.
+    pub snippet: bool,
+}
+
+/// The textures in `textures/particle/` named after `id` with a frame
+/// number, like vanilla's `generic_0` to `generic_7`, in frame order. The
+/// underscore before the number is optional.
+pub fn frames(project: &Project, id: &str) -> Vec<String> {
+    let dir = project.assets().join("textures").join("particle");
+    let mut frames: Vec<(u32, String)> = fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            let name = name.strip_suffix(".png")?.to_string();
+            let number = name.strip_prefix(id)?;
+            let number = number.strip_prefix('_').unwrap_or(number);
+            Some((number.parse().ok()?, name))
+        })
+        .collect();
+    frames.sort();
+    frames.into_iter().map(|(_, name)| name).collect()
+}
+
+/// The Java code registering the particle type, which still needs a
+/// provider registered on the client to show up.
+fn java_registration(project: &Project, particle: &ParticleSpec) -> String {
+    let version = project.version();
+    let loader = project.loader();
+    let field = particle.id.to_uppercase();
+    match loader {
+        Loader::Fabric => {
+            // Yarn named it DefaultParticleType until 1.20.5
+            let kind = if version.at_least(20, 5) {
+                "SimpleParticleType"
+            } else {
+                "DefaultParticleType"
+            };
+            let registry = if version.at_least(19, 3) {
+                "Registries.PARTICLE_TYPE"
+            } else {
+                "Registry.PARTICLE_TYPE"
+            };
+            let location = loader.java_location(version, project.namespace(), &particle.id);
+            format!(
+                "public static final {kind} {field} = FabricParticleTypes.simple({always});\n\n\
+                 Registry.register({registry}, {location}, {field});\n",
+                always = particle.always_show,
+            )
+        }
+        Loader::Forge | Loader::NeoForge => {
+            let holder = if loader.data_namespace(version) == Some("neoforge") {
+                "DeferredHolder<ParticleType<?>, SimpleParticleType>"
+            } else {
+                "RegistryObject<SimpleParticleType>"
+            };
+            format!(
+                "public static final {} {} =\n        \
+                 PARTICLE_TYPES.register(\"{}\", () -> new SimpleParticleType({}));\n",
+                holder, field, particle.id, particle.always_show
+            )
+        }
+    }
+}
+
+/// Writes the particle definition listing its frames, or the texture named
+/// like the particle when there are none, and its registration. Returns the
+/// files that were written and the number of frames found.
+pub fn generate(project: &Project, particle: &ParticleSpec) -> io::Result<(Vec<PathBuf>, usize)> {
+    let mut frames = frames(project, &particle.id);
+    let found = frames.len();
+    if frames.is_empty() {
+        frames.push(particle.id.clone());
+    }
+    let textures: Vec<String> = frames.iter().map(|frame| project.resource(frame)).collect();
+    let path = project
+        .assets()
+        .join("particles")
+        .join(format!("{}.json", particle.id));
+    let mut written = vec![project.write_json(&path, &json!({ "textures": textures }))?];
+    if particle.snippet {
+        let name = format!("{}_particle_type.java", particle.id);
+        written.push(project.write_snippet(&name, &java_registration(project, particle))?);
+    }
+    Ok((written, found))
+}
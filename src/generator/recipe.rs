@@ -0,0 +1,206 @@
+use std::{io, path::PathBuf};
+
+use serde_json::{json, Map, Value};
+
+use crate::{generator::advancement, project::Project, version::GameVersion};
+
+/// An ingredient matching `item`. 1.21.2 replaced the `{"item": ...}` object
+/// with the bare id.
+pub fn ingredient(version: GameVersion, item: &str) -> Value {
+    if version.at_least(21, 2) {
+        json!(item)
+    } else {
+        json!({ "item": item })
+    }
+}
+
+/// The result of a crafting recipe, which became an item stack with an `id`
+/// in 1.20.5.
+fn result(version: GameVersion, item: &str, count: u32) -> Value {
+    let mut result = Map::new();
+    let field = if version.at_least(20, 5) {
+        "id"
+    } else {
+        "item"
+    };
+    result.insert(field.into(), json!(item));
+    if count > 1 {
+        result.insert("count".into(), json!(count));
+    }
+    Value::Object(result)
+}
+
+/// Recipe book categories only exist from 1.19.3.
+fn categorize(version: GameVersion, recipe: &mut Map<String, Value>, category: &str) {
+    if version.at_least(19, 3) {
+        recipe.insert("category".into(), json!(category));
+    }
+}
+
+/// A `smelting` or `blasting` recipe, blasting taking half the time like
+/// vanilla does.
+pub fn cooking(
+    version: GameVersion,
+    kind: &str,
+    input: &str,
+    output: &str,
+    experience: f64,
+) -> Value {
+    let mut recipe = Map::new();
+    recipe.insert("type".into(), json!(format!("minecraft:{}", kind)));
+    categorize(version, &mut recipe, "misc");
+    let time = if kind == "smelting" { 200 } else { 100 };
+    recipe.insert("cookingtime".into(), json!(time));
+    recipe.insert("experience".into(), json!(experience));
+    recipe.insert("ingredient".into(), ingredient(version, input));
+    // Cooking results were a bare id until item stacks came in 1.20.5
+    let result = if version.at_least(20, 5) {
+        json!({ "id": output })
+    } else {
+        json!(output)
+    };
+    recipe.insert("result".into(), result);
+    Value::Object(recipe)
+}
+
+/// A shaped crafting recipe where `key` maps the pattern symbols to items.
+pub fn shaped(
+    version: GameVersion,
+    pattern: &[&str],
+    key: &[(char, &str)],
+    output: &str,
+    count: u32,
+) -> Value {
+    let mut recipe = Map::new();
+    recipe.insert("type".into(), json!("minecraft:crafting_shaped"));
+    categorize(version, &mut recipe, "building");
+    let key: Map<String, Value> = key
+        .iter()
+        .map(|(symbol, item)| (symbol.to_string(), ingredient(version, item)))
+        .collect();
+    recipe.insert("key".into(), Value::Object(key));
+    recipe.insert("pattern".into(), json!(pattern));
+    recipe.insert("result".into(), result(version, output, count));
+    Value::Object(recipe)
+}
+
+pub fn shapeless(version: GameVersion, ingredients: &[&str], output: &str, count: u32) -> Value {
+    let mut recipe = Map::new();
+    recipe.insert("type".into(), json!("minecraft:crafting_shapeless"));
+    categorize(version, &mut recipe, "misc");
+    let ingredients = ingredients
+        .iter()
+        .map(|item| ingredient(version, item))
+        .collect();
+    recipe.insert("ingredients".into(), Value::Array(ingredients));
+    recipe.insert("result".into(), result(version, output, count));
+    Value::Object(recipe)
+}
+
+/// The items and `#tags` of the ingredients of `recipe`, in the order they
+/// first show up. Alternatives in an ingredient list are all included.
+pub fn ingredients(recipe: &Value) -> Vec<String> {
+    fn collect(ingredient: &Value, ids: &mut Vec<String>) {
+        let id = match ingredient {
+            Value::String(id) => id.clone(),
+            Value::Array(alternatives) => {
+                alternatives.iter().for_each(|alt| collect(alt, ids));
+                return;
+            }
+            Value::Object(object) => match (object.get("item"), object.get("tag")) {
+                (Some(Value::String(item)), _) => item.clone(),
+                (_, Some(Value::String(tag))) => format!("#{}", tag),
+                _ => return,
+            },
+            _ => return,
+        };
+        if !ids.contains(&id) {
+            ids.push(id);
+        }
+    }
+    let mut ids = Vec::new();
+    for field in ["ingredient", "ingredients", "base", "addition"] {
+        if let Some(ingredient) = recipe.get(field) {
+            collect(ingredient, &mut ids);
+        }
+    }
+    if let Some(Value::Object(key)) = recipe.get("key") {
+        key.values()
+            .for_each(|ingredient| collect(ingredient, &mut ids));
+    }
+    ids
+}
+
+/// The recipe book tab folder vanilla files the unlock advancement of a
+/// recipe in.
+fn unlock_category(recipe: &Value) -> &'static str {
+    match recipe.get("category").and_then(Value::as_str) {
+        Some("building" | "blocks") => "building_blocks",
+        Some("equipment") => "tools",
+        Some("food") => "food",
+        Some("redstone") => "redstone",
+        _ => "misc",
+    }
+}
+
+/// The advancement that unlocks recipe `name` in the recipe book once the
+/// player holds any of `items`, or learned the recipe some other way.
+/// `items` may hold `#tags`.
+pub fn unlock_advancement(project: &Project, name: &str, items: &[String]) -> Value {
+    let version = project.version();
+    let id = project.resource(name);
+    let mut criteria = Map::new();
+    criteria.insert(
+        "has_the_recipe".into(),
+        json!({
+            "trigger": "minecraft:recipe_unlocked",
+            "conditions": { "recipe": id }
+        }),
+    );
+    for item in items {
+        let predicate = match item.strip_prefix('#') {
+            // Tags went into `items` next to the ids in 1.20.5
+            Some(_) if version.at_least(20, 5) => json!({ "items": item }),
+            Some(tag) => json!({ "tag": tag }),
+            None => advancement::item_predicate(version, item),
+        };
+        let path = item.rsplit(':').next().unwrap_or_default();
+        criteria.insert(
+            format!("has_{}", path.replace('/', "_")),
+            json!({
+                "trigger": "minecraft:inventory_changed",
+                "conditions": { "items": [predicate] }
+            }),
+        );
+    }
+    let names: Vec<&String> = criteria.keys().collect();
+    json!({
+        "parent": "minecraft:recipes/root",
+        "criteria": criteria,
+        "requirements": [names],
+        "rewards": { "recipes": [id] }
+    })
+}
+
+/// Writes `recipe` to the recipe folder as `<name>.json`. With `unlock` its
+/// ingredients also unlock it through an advancement in
+/// `advancement/recipes/<category>/`.
+pub fn write(
+    project: &Project,
+    name: &str,
+    recipe: &Value,
+    unlock: bool,
+) -> io::Result<Vec<PathBuf>> {
+    let path = project.data_folder("recipe").join(format!("{}.json", name));
+    let mut written = vec![project.write_json(&path, recipe)?];
+    if unlock {
+        let path = project
+            .data_folder("advancement")
+            .join("recipes")
+            .join(unlock_category(recipe))
+            .join(format!("{}.json", name));
+        let advancement = unlock_advancement(project, name, &ingredients(recipe));
+        written.push(project.write_json(&path, &advancement)?);
+    }
+    Ok(written)
+}
@@ -0,0 +1,205 @@
+use std::{io, path::PathBuf};
+
+use serde_json::{json, Map, Value};
+
+use crate::{
+    loader::Loader,
+    project::{self, Project},
+};
+
+/// An `.ogg` file the sound event picks from when played.
+pub struct SoundFile {
+    /// The path under `sounds/` without the extension, as `namespace:path`.
+    name: String,
+    volume: f64,
+    pitch: f64,
+    /// How likely it is to be picked over the other files.
+    weight: u32,
+    /// Streamed files are read while playing, which suits long music.
+    stream: bool,
+}
+
+impl SoundFile {
+    /// Parses rows written as `path [volume] [pitch] [weight] [stream]`, such
+    /// as `block/chime1 0.8 1.2 2`. Paths without a namespace are the
+    /// project's.
+    pub fn parse(project: &Project, row: &str) -> Result<Self, String> {
+        let words: Vec<&str> = row.split_whitespace().collect();
+        let invalid = || {
+            format!(
+                "Sound '{}' should be written as 'path [volume] [pitch] [weight] [stream]'.",
+                row
+            )
+        };
+        let (path, rest) = words.split_first().ok_or_else(invalid)?;
+        let (rest, stream) = match rest.split_last() {
+            Some((&"stream", rest)) => (rest, true),
+            _ => (rest, false),
+        };
+        if rest.len() > 3 {
+            return Err(invalid());
+        }
+        let number = |idx: usize, default: f64| match rest.get(idx) {
+            Some(word) => word.parse::<f64>().map_err(|_| invalid()),
+            None => Ok(default),
+        };
+        let (volume, pitch) = (number(0, 1.0)?, number(1, 1.0)?);
+        let weight = match rest.get(2) {
+            Some(word) => word.parse().map_err(|_| invalid())?,
+            None => 1,
+        };
+        if !(0.0..=1.0).contains(&volume) || pitch <= 0.0 || weight == 0 {
+            return Err(format!("Sound '{}' is out of range.", row));
+        }
+        let path = path.trim_end_matches(".ogg");
+        let name = if path.contains(':') {
+            path.to_string()
+        } else {
+            project.resource(path)
+        };
+        Ok(Self {
+            name,
+            volume,
+            pitch,
+            weight,
+            stream,
+        })
+    }
+
+    /// The bare name when every field is the default, like vanilla writes
+    /// most of its sounds.
+    fn json(&self) -> Value {
+        let mut sound = Map::new();
+        sound.insert("name".into(), json!(self.name));
+        if self.volume != 1.0 {
+            sound.insert("volume".into(), json!(self.volume));
+        }
+        if self.pitch != 1.0 {
+            sound.insert("pitch".into(), json!(self.pitch));
+        }
+        if self.weight != 1 {
+            sound.insert("weight".into(), json!(self.weight));
+        }
+        if self.stream {
+            sound.insert("stream".into(), json!(true));
+        }
+        if sound.len() == 1 {
+            json!(self.name)
+        } else {
+            Value::Object(sound)
+        }
+    }
+}
+
+/// Everything needed to generate a sound event.
+pub struct SoundSpec {
+    /// The event id, usually dotted like `block.ruby_chime.ring`.
+    pub id: String,
+    /// The caption shown with subtitles on, none when empty.
+    pub subtitle: String,
+    pub sounds: Vec<SoundFile>,
+    /// Whether the files replace the ones resource packs below define for
+    /// the same event instead of adding to them.
+    pub replace: bool,
+    pub lang: bool,
+    /// Whether to write the Java registration of the event.
+    pub snippet: bool,
+}
+
+impl SoundSpec {
+    fn subtitle_key(&self, project: &Project) -> String {
+        format!("subtitles.{}.{}", project.namespace(), self.id)
+    }
+
+    fn json(&self, project: &Project) -> Value {
+        let mut event = Map::new();
+        if self.replace {
+            event.insert("replace".into(), json!(true));
+        }
+        if !self.subtitle.is_empty() {
+            event.insert("subtitle".into(), json!(self.subtitle_key(project)));
+        }
+        let sounds = self.sounds.iter().map(SoundFile::json).collect();
+        event.insert("sounds".into(), Value::Array(sounds));
+        Value::Object(event)
+    }
+
+    /// The project's files the event refers to that don't exist yet.
+    pub fn missing_files(&self, project: &Project) -> Vec<String> {
+        let prefix = project.resource("");
+        self.sounds
+            .iter()
+            .filter_map(|sound| sound.name.strip_prefix(&prefix))
+            .map(|path| format!("sounds/{}.ogg", path))
+            .filter(|path| !project.assets().join(path).exists())
+            .collect()
+    }
+}
+
+/// The Java code registering the event. The event's range grows with its
+/// volume from 1.19.3, which vanilla uses for nearly all sounds.
+fn java_registration(project: &Project, sound: &SoundSpec) -> String {
+    let version = project.version();
+    let loader = project.loader();
+    let field = sound.id.replace(['.', '/'], "_").to_uppercase();
+    let location = loader.java_location(version, project.namespace(), &sound.id);
+    let modern = version.at_least(19, 3);
+    match loader {
+        Loader::Fabric => {
+            let (registry, event) = if modern {
+                (
+                    "Registries.SOUND_EVENT",
+                    format!("SoundEvent.of({}_ID)", field),
+                )
+            } else {
+                (
+                    "Registry.SOUND_EVENT",
+                    format!("new SoundEvent({}_ID)", field),
+                )
+            };
+            format!(
+                "public static final Identifier {field}_ID = {location};\n\
+                 public static final SoundEvent {field} =\n        \
+                 Registry.register({registry}, {field}_ID, {event});\n"
+            )
+        }
+        Loader::Forge | Loader::NeoForge => {
+            let event = if modern {
+                format!("SoundEvent.createVariableRangeEvent({})", location)
+            } else {
+                format!("new SoundEvent({})", location)
+            };
+            // NeoForge swapped RegistryObject for DeferredHolder with its
+            // own registries
+            let holder = if loader.data_namespace(version) == Some("neoforge") {
+                "DeferredHolder<SoundEvent, SoundEvent>"
+            } else {
+                "RegistryObject<SoundEvent>"
+            };
+            format!(
+                "public static final {} {} =\n        \
+                 SOUND_EVENTS.register(\"{}\", () -> {});\n",
+                holder, field, sound.id, event
+            )
+        }
+    }
+}
+
+/// Merges the event into `sounds.json` and writes its subtitle and
+/// registration, returning the files that were written.
+pub fn generate(project: &Project, sound: &SoundSpec) -> io::Result<Vec<PathBuf>> {
+    let mut written = Vec::new();
+    let path = project.assets().join("sounds.json");
+    let mut sounds = project::read_json_object(&path)?;
+    sounds.insert(sound.id.clone(), sound.json(project));
+    written.push(project.write_json(&path, &Value::Object(sounds))?);
+    if sound.lang && !sound.subtitle.is_empty() {
+        let key = sound.subtitle_key(project);
+        written.push(project.add_translation(&key, &sound.subtitle)?);
+    }
+    if sound.snippet {
+        let name = format!("{}_sound_event.java", sound.id);
+        written.push(project.write_snippet(&name, &java_registration(project, sound))?);
+    }
+    Ok(written)
+}
@@ -0,0 +1,450 @@
+use std::{io, path::PathBuf};
+
+use serde_json::{json, Map, Value};
+
+use crate::{generator::model, project::Project};
+
+/// How a feature field is typed in and written to the JSON.
+pub enum FieldKind {
+    Int {
+        min: i64,
+        max: i64,
+    },
+    Float {
+        min: f64,
+        max: f64,
+    },
+    Bool,
+    /// A block state such as `{"Name": "minecraft:stone"}`.
+    Block,
+    /// Comma separated block ids.
+    Blocks,
+    /// A `simple_state_provider` placing a single block.
+    Provider,
+    /// A resource location, such as a tag or another feature.
+    Id,
+}
+
+/// A field of a feature config.
+pub struct Field {
+    /// Where the value goes inside `config`, nested with dots. Numbers index
+    /// into arrays of the template.
+    pub path: &'static str,
+    pub desc: &'static str,
+    pub kind: FieldKind,
+    pub default: &'static str,
+    pub required: bool,
+}
+
+/// A vanilla feature type, with the parts of its config that can't be
+/// edited already filled in by its template.
+pub struct FeatureType {
+    pub name: &'static str,
+    template: fn() -> Value,
+    pub fields: &'static [Field],
+}
+
+const fn field(
+    path: &'static str,
+    desc: &'static str,
+    kind: FieldKind,
+    default: &'static str,
+) -> Field {
+    Field {
+        path,
+        desc,
+        kind,
+        default,
+        required: true,
+    }
+}
+
+/// A field left out of the JSON when empty, for the game to default.
+const fn optional(
+    path: &'static str,
+    desc: &'static str,
+    kind: FieldKind,
+    default: &'static str,
+) -> Field {
+    Field {
+        path,
+        desc,
+        kind,
+        default,
+        required: false,
+    }
+}
+
+const fn int(min: i64, max: i64) -> FieldKind {
+    FieldKind::Int { min, max }
+}
+
+pub const FEATURE_TYPES: [FeatureType; 5] = [
+    FeatureType {
+        name: "tree",
+        template: || {
+            json!({
+                "decorators": [],
+                "minimum_size": {
+                    "type": "minecraft:two_layers_feature_size",
+                    "limit": 1,
+                    "lower_size": 0,
+                    "upper_size": 1,
+                },
+                "trunk_placer": { "type": "minecraft:straight_trunk_placer" },
+                "foliage_placer": { "type": "minecraft:blob_foliage_placer" },
+            })
+        },
+        fields: &[
+            field(
+                "trunk_provider",
+                "The log block.",
+                FieldKind::Provider,
+                "minecraft:oak_log",
+            ),
+            field(
+                "foliage_provider",
+                "The leaves block.",
+                FieldKind::Provider,
+                "minecraft:oak_leaves",
+            ),
+            field(
+                "dirt_provider",
+                "The block placed under the trunk.",
+                FieldKind::Provider,
+                "minecraft:dirt",
+            ),
+            field(
+                "trunk_placer.base_height",
+                "The shortest trunk.",
+                int(0, 32),
+                "4",
+            ),
+            field(
+                "trunk_placer.height_rand_a",
+                "Random extra trunk height.",
+                int(0, 24),
+                "2",
+            ),
+            field(
+                "trunk_placer.height_rand_b",
+                "More random extra trunk height.",
+                int(0, 24),
+                "0",
+            ),
+            field(
+                "foliage_placer.radius",
+                "How far leaves reach from the trunk.",
+                int(0, 16),
+                "2",
+            ),
+            field(
+                "foliage_placer.offset",
+                "How far below the top leaves start.",
+                int(0, 16),
+                "0",
+            ),
+            field(
+                "foliage_placer.height",
+                "How many layers of leaves.",
+                int(0, 16),
+                "3",
+            ),
+            field(
+                "ignore_vines",
+                "Whether vines don't block the tree.",
+                FieldKind::Bool,
+                "true",
+            ),
+            field(
+                "force_dirt",
+                "Whether the dirt block replaces any ground.",
+                FieldKind::Bool,
+                "false",
+            ),
+        ],
+    },
+    FeatureType {
+        name: "random_patch",
+        template: || json!({}),
+        fields: &[
+            field(
+                "feature",
+                "The placed feature scattered around, like a flower.",
+                FieldKind::Id,
+                "",
+            ),
+            optional("tries", "How many times it is placed.", int(1, 1024), "96"),
+            optional("xz_spread", "How far it spreads sideways.", int(0, 16), "7"),
+            optional(
+                "y_spread",
+                "How far it spreads up and down.",
+                int(0, 16),
+                "3",
+            ),
+        ],
+    },
+    FeatureType {
+        name: "disk",
+        template: || {
+            json!({
+                "state_provider": { "rules": [] },
+                "target": { "type": "minecraft:matching_blocks" },
+                "radius": { "type": "minecraft:uniform" },
+            })
+        },
+        fields: &[
+            field(
+                "state_provider.fallback",
+                "The block the disk is made of.",
+                FieldKind::Provider,
+                "minecraft:clay",
+            ),
+            field(
+                "target.blocks",
+                "Comma separated blocks the disk replaces.",
+                FieldKind::Blocks,
+                "minecraft:dirt, minecraft:clay",
+            ),
+            field(
+                "radius.min_inclusive",
+                "The smallest radius.",
+                int(0, 8),
+                "2",
+            ),
+            field(
+                "radius.max_inclusive",
+                "The largest radius.",
+                int(0, 8),
+                "3",
+            ),
+            field(
+                "half_height",
+                "How far the disk reaches up and down.",
+                int(0, 4),
+                "1",
+            ),
+        ],
+    },
+    FeatureType {
+        name: "ore",
+        template: || {
+            json!({
+                "targets": [{
+                    "target": { "predicate_type": "minecraft:tag_match" },
+                }]
+            })
+        },
+        fields: &[
+            field("targets.0.state", "The ore block.", FieldKind::Block, ""),
+            field(
+                "targets.0.target.tag",
+                "The tag of blocks the ore replaces.",
+                FieldKind::Id,
+                "minecraft:stone_ore_replaceables",
+            ),
+            field(
+                "size",
+                "How many blocks a vein has at most.",
+                int(0, 64),
+                "9",
+            ),
+            optional(
+                "discard_chance_on_air_exposure",
+                "Chance of skipping blocks touching air.",
+                FieldKind::Float { min: 0.0, max: 1.0 },
+                "0",
+            ),
+        ],
+    },
+    FeatureType {
+        name: "simple_block",
+        template: || json!({}),
+        fields: &[field(
+            "to_place",
+            "The block placed.",
+            FieldKind::Provider,
+            "",
+        )],
+    },
+];
+
+/// Names of the placement modifiers rows can use, with their arguments.
+pub const PLACEMENT_MODIFIERS: [&str; 6] = [
+    "count <n>",
+    "rarity_filter <chance>",
+    "in_square",
+    "heightmap <type>",
+    "height_range <min> <max>",
+    "biome",
+];
+
+const HEIGHTMAPS: [&str; 6] = [
+    "MOTION_BLOCKING",
+    "MOTION_BLOCKING_NO_LEAVES",
+    "OCEAN_FLOOR",
+    "OCEAN_FLOOR_WG",
+    "WORLD_SURFACE",
+    "WORLD_SURFACE_WG",
+];
+
+impl FeatureType {
+    pub fn parse(name: &str) -> Option<&'static Self> {
+        FEATURE_TYPES.iter().find(|kind| kind.name == name)
+    }
+
+    /// Builds the configured feature from the raw `(path, value)` pairs typed
+    /// for each field, failing when a required one is empty.
+    pub fn configured(
+        &self,
+        project: &Project,
+        values: &[(&str, String)],
+    ) -> Result<Value, String> {
+        let mut config = (self.template)();
+        for field in self.fields {
+            let raw = values
+                .iter()
+                .find(|(path, _)| *path == field.path)
+                .map_or("", |(_, value)| value.trim());
+            if raw.is_empty() {
+                if field.required {
+                    return Err(format!(
+                        "{} is required for {} features.",
+                        field.path, self.name
+                    ));
+                }
+                continue;
+            }
+            let invalid = || format!("'{}' is not a valid {}.", raw, field.path);
+            let value = match field.kind {
+                FieldKind::Int { .. } => json!(raw.parse::<i64>().map_err(|_| invalid())?),
+                FieldKind::Float { .. } => json!(raw.parse::<f64>().map_err(|_| invalid())?),
+                FieldKind::Bool => json!(raw.parse::<bool>().map_err(|_| invalid())?),
+                FieldKind::Block => json!({ "Name": model::location(project, raw) }),
+                FieldKind::Blocks => raw
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|block| !block.is_empty())
+                    .map(|block| json!(model::location(project, block)))
+                    .collect(),
+                FieldKind::Provider => json!({
+                    "type": "minecraft:simple_state_provider",
+                    "state": { "Name": model::location(project, raw) },
+                }),
+                FieldKind::Id => json!(model::location(project, raw)),
+            };
+            set(&mut config, field.path, value);
+        }
+        Ok(json!({
+            "type": format!("minecraft:{}", self.name),
+            "config": config,
+        }))
+    }
+}
+
+/// Puts `value` at the dotted `path`, creating the objects on the way.
+fn set(node: &mut Value, path: &str, value: Value) {
+    let mut node = node;
+    let mut parts = path.split('.').peekable();
+    while let Some(part) = parts.next() {
+        let last = parts.peek().is_none();
+        let next = match node {
+            Value::Array(values) => match part.parse::<usize>() {
+                Ok(idx) if idx < values.len() => &mut values[idx],
+                _ => return,
+            },
+            Value::Object(map) if last => {
+                map.insert(part.to_string(), value);
+                return;
+            }
+            Value::Object(map) => map.entry(part).or_insert_with(|| Value::Object(Map::new())),
+            _ => return,
+        };
+        if last {
+            *next = value;
+            return;
+        }
+        node = next;
+    }
+}
+
+/// Parses placement modifier rows such as `count 4` or `heightmap
+/// WORLD_SURFACE_WG`, see `PLACEMENT_MODIFIERS`.
+pub fn placement(rows: &[String]) -> Result<Vec<Value>, String> {
+    let mut modifiers = Vec::new();
+    for row in rows
+        .iter()
+        .map(|row| row.trim())
+        .filter(|row| !row.is_empty())
+    {
+        let words: Vec<&str> = row.split_whitespace().collect();
+        let int = |word: &str| {
+            word.parse::<i64>()
+                .map_err(|_| format!("'{}' is not a whole number in '{}'.", word, row))
+        };
+        let modifier = match words.as_slice() {
+            ["count", count] => json!({ "type": "minecraft:count", "count": int(count)? }),
+            ["rarity_filter", chance] => {
+                json!({ "type": "minecraft:rarity_filter", "chance": int(chance)? })
+            }
+            ["in_square"] => json!({ "type": "minecraft:in_square" }),
+            ["heightmap", heightmap] => {
+                let heightmap = heightmap.to_uppercase();
+                if !HEIGHTMAPS.contains(&heightmap.as_str()) {
+                    return Err(format!(
+                        "'{}' is not one of {}.",
+                        heightmap,
+                        HEIGHTMAPS.join(", ")
+                    ));
+                }
+                json!({ "type": "minecraft:heightmap", "heightmap": heightmap })
+            }
+            ["height_range", min, max] => json!({
+                "type": "minecraft:height_range",
+                "height": {
+                    "type": "minecraft:uniform",
+                    "min_inclusive": { "absolute": int(min)? },
+                    "max_inclusive": { "absolute": int(max)? },
+                }
+            }),
+            ["biome"] => json!({ "type": "minecraft:biome" }),
+            _ => {
+                return Err(format!(
+                    "'{}' should be one of {}.",
+                    row,
+                    PLACEMENT_MODIFIERS.join(", ")
+                ))
+            }
+        };
+        modifiers.push(modifier);
+    }
+    Ok(modifiers)
+}
+
+/// Writes `data/<namespace>/worldgen/<registry>/<id>.json`.
+pub fn write(project: &Project, registry: &str, id: &str, value: &Value) -> io::Result<PathBuf> {
+    let path = project
+        .data()
+        .join("worldgen")
+        .join(registry)
+        .join(format!("{}.json", id));
+    project.write_json(&path, value)
+}
+
+/// Writes the configured feature and the placed feature using it, both
+/// called `id`.
+pub fn generate(
+    project: &Project,
+    id: &str,
+    configured: &Value,
+    placement: Vec<Value>,
+) -> io::Result<Vec<PathBuf>> {
+    let placed = json!({
+        "feature": project.resource(id),
+        "placement": placement,
+    });
+    Ok(vec![
+        write(project, "configured_feature", id, configured)?,
+        write(project, "placed_feature", id, &placed)?,
+    ])
+}
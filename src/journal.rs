@@ -0,0 +1,307 @@
+use std::{
+    cell::RefCell,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use serde_json::{json, Value};
+
+use crate::project;
+
+/// How many generations can be undone, the oldest ones being forgotten.
+const LIMIT: usize = 50;
+
+/// The folder of the undo journal, under the project root.
+fn undo_stack(root: &Path) -> PathBuf {
+    root.join(".mcdevtools").join("journal")
+}
+
+/// The folder of the changes undone, which redo brings back.
+fn redo_stack(root: &Path) -> PathBuf {
+    undo_stack(root).join("redo")
+}
+
+/// The numbered entries of `stack`, oldest first.
+fn entries(stack: &Path) -> Vec<(u64, PathBuf)> {
+    let mut entries: Vec<(u64, PathBuf)> = fs::read_dir(stack)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|entry| {
+            let number = entry.file_name().to_str()?.parse().ok()?;
+            Some((number, entry.path()))
+        })
+        .collect();
+    entries.sort();
+    entries
+}
+
+/// A file an entry touched, and the copy of what it held before, if it
+/// existed.
+struct Record {
+    path: String,
+    before: Option<String>,
+}
+
+/// One generation: the files it touched, saved to `journal.json` in its
+/// folder along with the copies, after every file so nothing is lost if the
+/// tool stops halfway.
+struct Entry {
+    dir: PathBuf,
+    records: Vec<Record>,
+}
+
+impl Entry {
+    fn read(dir: &Path) -> io::Result<Vec<Record>> {
+        let json = project::read_json_object(&dir.join("journal.json"))?;
+        let records = json.get("files").and_then(Value::as_array);
+        Ok(records
+            .into_iter()
+            .flatten()
+            .filter_map(|record| {
+                Some(Record {
+                    path: record.get("path")?.as_str()?.to_string(),
+                    before: record
+                        .get("before")
+                        .and_then(Value::as_str)
+                        .map(String::from),
+                })
+            })
+            .collect())
+    }
+
+    fn save(&self) -> io::Result<()> {
+        let files: Vec<Value> = self
+            .records
+            .iter()
+            .map(|record| json!({ "path": record.path, "before": record.before }))
+            .collect();
+        let contents = serde_json::to_string_pretty(&json!({ "files": files }))?;
+        fs::write(self.dir.join("journal.json"), contents)
+    }
+}
+
+/// Records the files a generation is about to create, change or delete so
+/// it can be undone, even after restarting the tool. Every project opened
+/// for an action gets its own, making one journal entry per action.
+pub struct Journal {
+    root: PathBuf,
+    stack: PathBuf,
+    /// The redo stack, dropped once something new is generated.
+    clears: Option<PathBuf>,
+    entry: RefCell<Option<Entry>>,
+}
+
+impl Journal {
+    /// The journal of the generations made in the project at `root`.
+    pub fn new(root: &Path) -> Self {
+        Self {
+            root: root.to_path_buf(),
+            stack: undo_stack(root),
+            clears: Some(redo_stack(root)),
+            entry: RefCell::new(None),
+        }
+    }
+
+    fn open_entry(&self) -> io::Result<Entry> {
+        if let Some(redo) = &self.clears {
+            if redo.exists() {
+                fs::remove_dir_all(redo)?;
+            }
+        }
+        let entries = entries(&self.stack);
+        for (_, dir) in entries
+            .iter()
+            .take((entries.len() + 1).saturating_sub(LIMIT))
+        {
+            fs::remove_dir_all(dir)?;
+        }
+        let number = entries.last().map_or(1, |(number, _)| number + 1);
+        let dir = self.stack.join(format!("{:06}", number));
+        fs::create_dir_all(&dir)?;
+        Ok(Entry {
+            dir,
+            records: Vec::new(),
+        })
+    }
+
+    /// Saves what `path` holds before it's written to or deleted. Only the
+    /// first change of each file counts, since undoing restores the state
+    /// from before the whole generation.
+    pub fn record(&self, path: &Path) -> io::Result<()> {
+        if path.starts_with(undo_stack(&self.root)) {
+            return Ok(());
+        }
+        let mut entry = self.entry.borrow_mut();
+        if entry.is_none() {
+            *entry = Some(self.open_entry()?);
+        }
+        let Some(entry) = entry.as_mut() else {
+            return Ok(());
+        };
+        let relative = path.strip_prefix(&self.root).unwrap_or(path);
+        let relative = relative.to_string_lossy().replace('\\', "/");
+        if entry.records.iter().any(|record| record.path == relative) {
+            return Ok(());
+        }
+        let before = if path.is_file() {
+            let name = entry.records.len().to_string();
+            fs::copy(path, entry.dir.join(&name))?;
+            Some(name)
+        } else {
+            None
+        };
+        entry.records.push(Record {
+            path: relative,
+            before,
+        });
+        entry.save()
+    }
+}
+
+/// Puts the files of the newest entry of `from` back how they were before
+/// it, recording what that overwrites in `to` so it can be taken back too.
+/// Returns how many files were restored, `None` when there is nothing to
+/// restore.
+fn restore(root: &Path, from: &Path, to: Journal) -> io::Result<Option<usize>> {
+    let Some((_, dir)) = entries(from).pop() else {
+        return Ok(None);
+    };
+    let records = Entry::read(&dir)?;
+    for record in records.iter().rev() {
+        let path = root.join(&record.path);
+        to.record(&path)?;
+        match &record.before {
+            Some(name) => {
+                if let Some(parent) = path.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+                fs::copy(dir.join(name), &path)?;
+            }
+            None if path.is_file() => {
+                fs::remove_file(&path)?;
+                project::remove_empty_parents(&path, root);
+            }
+            None => (),
+        }
+    }
+    fs::remove_dir_all(&dir)?;
+    Ok(Some(records.len()))
+}
+
+/// Undoes the last generation made in the project at `root`.
+pub fn undo(root: &Path) -> io::Result<Option<usize>> {
+    let redo = Journal {
+        root: root.to_path_buf(),
+        stack: redo_stack(root),
+        clears: None,
+        entry: RefCell::new(None),
+    };
+    restore(root, &undo_stack(root), redo)
+}
+
+/// Brings back the last generation undone in the project at `root`.
+pub fn redo(root: &Path) -> io::Result<Option<usize>> {
+    let undo = Journal {
+        clears: None,
+        ..Journal::new(root)
+    };
+    restore(root, &redo_stack(root), undo)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{loader::Loader, project::Project, version::GameVersion};
+
+    /// An empty project folder of its own for each test.
+    fn root(name: &str) -> PathBuf {
+        let root = std::env::temp_dir().join(format!(
+            "mc-devtools-journal-{}-{}",
+            std::process::id(),
+            name
+        ));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(&root).unwrap();
+        root
+    }
+
+    /// Opens the project for a single action, like the app does.
+    fn project(root: &Path) -> Project {
+        Project::new(root, "ruby", GameVersion::new(20, 1), Loader::Forge)
+    }
+
+    fn read(path: &Path) -> Option<String> {
+        fs::read_to_string(path).ok()
+    }
+
+    #[test]
+    fn undo_and_redo_a_generation() {
+        let root = root("generation");
+        let (a, b) = (root.join("assets/a.json"), root.join("assets/b.json"));
+        fs::create_dir_all(a.parent().unwrap()).unwrap();
+        fs::write(&a, "old").unwrap();
+        {
+            let project = project(&root);
+            project.write(&a, "new").unwrap();
+            project.write(&a, "newer").unwrap();
+            project.write(&b, "created").unwrap();
+        }
+
+        assert_eq!(undo(&root).unwrap(), Some(2));
+        assert_eq!(read(&a).as_deref(), Some("old"));
+        assert_eq!(read(&b), None);
+        assert_eq!(undo(&root).unwrap(), None);
+
+        assert_eq!(redo(&root).unwrap(), Some(2));
+        assert_eq!(read(&a).as_deref(), Some("newer"));
+        assert_eq!(read(&b).as_deref(), Some("created"));
+        assert_eq!(redo(&root).unwrap(), None);
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn undo_removals_and_moves() {
+        let root = root("moves");
+        let (a, b) = (root.join("a.json"), root.join("nested/b.json"));
+        fs::write(&a, "a").unwrap();
+        project(&root).rename(&a, &b).unwrap();
+        assert_eq!(read(&b).as_deref(), Some("a"));
+        project(&root).remove(&b).unwrap();
+
+        assert_eq!(undo(&root).unwrap(), Some(1));
+        assert_eq!(read(&b).as_deref(), Some("a"));
+        assert_eq!(undo(&root).unwrap(), Some(2));
+        assert_eq!(read(&a).as_deref(), Some("a"));
+        assert_eq!(read(&b), None);
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn new_generations_drop_the_redo_stack() {
+        let root = root("redo");
+        let a = root.join("a.json");
+        project(&root).write(&a, "1").unwrap();
+        undo(&root).unwrap();
+        project(&root).write(&a, "2").unwrap();
+        assert_eq!(redo(&root).unwrap(), None);
+        assert_eq!(read(&a).as_deref(), Some("2"));
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn keeps_the_last_generations_only() {
+        let root = root("limit");
+        let a = root.join("a.json");
+        for generation in 0..LIMIT + 5 {
+            project(&root).write(&a, &generation.to_string()).unwrap();
+        }
+        assert_eq!(entries(&undo_stack(&root)).len(), LIMIT);
+        for _ in 0..LIMIT {
+            assert!(undo(&root).unwrap().is_some());
+        }
+        assert_eq!(undo(&root).unwrap(), None);
+        assert_eq!(read(&a).as_deref(), Some("4"));
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,63 @@
+use std::fmt;
+
+use crate::version::GameVersion;
+
+/// The mod loader the project targets, deciding where loader specific files
+/// go and which snippets are written.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Loader {
+    Forge,
+    NeoForge,
+    Fabric,
+}
+
+impl Loader {
+    pub const ALL: [Loader; 3] = [Self::Forge, Self::NeoForge, Self::Fabric];
+
+    /// The loader called `name`, ignoring case.
+    pub fn parse(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|loader| loader.name().eq_ignore_ascii_case(name.trim()))
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Forge => "Forge",
+            Self::NeoForge => "NeoForge",
+            Self::Fabric => "Fabric",
+        }
+    }
+    /// The loader after this one, wrapping around.
+    pub fn next(&self) -> Self {
+        Self::ALL[(*self as usize + 1) % Self::ALL.len()]
+    }
+    /// The namespace of the loader's own data, such as `forge:add_features`,
+    /// or `None` for Fabric which registers everything in code.
+    pub fn data_namespace(&self, version: GameVersion) -> Option<&'static str> {
+        match self {
+            // NeoForge kept the Forge namespace until its 1.20.2 release
+            Self::NeoForge if version.at_least(20, 2) => Some("neoforge"),
+            Self::Forge | Self::NeoForge => Some("forge"),
+            Self::Fabric => None,
+        }
+    }
+    /// The Java expression for the `namespace:path` id, a Yarn `Identifier`
+    /// on Fabric and a `ResourceLocation` otherwise. Both lost their public
+    /// constructor in 1.21.
+    pub fn java_location(&self, version: GameVersion, namespace: &str, path: &str) -> String {
+        let constructor = match self {
+            Self::Fabric if version.at_least(21, 0) => "Identifier.of",
+            Self::Fabric => "new Identifier",
+            _ if version.at_least(21, 0) => "ResourceLocation.fromNamespaceAndPath",
+            _ => "new ResourceLocation",
+        };
+        format!("{}(\"{}\", \"{}\")", constructor, namespace, path)
+    }
+}
+
+impl fmt::Display for Loader {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
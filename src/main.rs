@@ -1,31 +1,51 @@
 use app::App;
+use check::{unused::Unused, Problem};
 use crossterm::{
     event::{self, Event, KeyCode},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
+use generator::{item_model::TreeRow, worldgen};
+use refactor::Change;
+use state::TextFieldState;
 use std::{
-    io,
+    env, io,
+    path::{Path, PathBuf},
+    process,
     time::{Duration, Instant},
 };
 use std::{io::Stdout, vec};
 use tui::{
     backend::{Backend, CrosstermBackend},
-    layout::{Constraint, Layout},
+    layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Style},
     text::{Span, Spans, Text},
-    widgets::{List, ListItem, Paragraph},
+    widgets::{ListItem, Paragraph, Wrap},
     Frame, Terminal,
 };
 use unicode_width::UnicodeWidthStr;
 use util::{
-    bold, create_menu, italic, menu_spans, move_menu_spans, stop_editing_spans, text_field,
-    AvailableOption, EditMode, MenuType,
+    bold, create_menu, italic, key_spans, menu_spans, move_menu_spans, option_editing_spans,
+    row_spans, stop_editing_spans, text_field, text_field_spans, AvailableOption, EditMode,
+    MenuType, StatefulList,
 };
 mod app;
+mod check;
+mod export;
+mod generator;
+mod journal;
+mod loader;
+mod project;
+mod refactor;
+mod settings;
 mod state;
 mod util;
+mod version;
 fn main() -> Result<(), io::Error> {
+    let args: Vec<String> = env::args().skip(1).collect();
+    if !args.is_empty() {
+        process::exit(run_headless(&args));
+    }
     enable_raw_mode()?;
     let mut stdout = io::stdout();
     execute!(stdout, EnterAlternateScreen)?;
@@ -38,6 +58,37 @@ fn main() -> Result<(), io::Error> {
     Ok(())
 }
 
+/// Runs the command given on the command line instead of the TUI, returning
+/// the exit code.
+fn run_headless(args: &[String]) -> i32 {
+    let usage = "Usage: mc-devtools export [pack folder] [output.zip]";
+    match args {
+        [command, rest @ ..] if command == "export" && rest.len() <= 2 => {
+            let root = rest
+                .first()
+                .map_or_else(|| PathBuf::from("."), PathBuf::from);
+            let root = root.canonicalize().unwrap_or(root);
+            let output = rest
+                .get(1)
+                .map_or_else(|| export::default_output(&root), PathBuf::from);
+            match export::zip_pack(&root, &output) {
+                Ok(count) => {
+                    println!("Exported {} file(s) to {}.", count, output.display());
+                    0
+                }
+                Err(err) => {
+                    eprintln!("{}", err);
+                    1
+                }
+            }
+        }
+        _ => {
+            eprintln!("{}", usage);
+            2
+        }
+    }
+}
+
 // Render method, this is the main loop that renders all the TUI
 fn render(
     app: &mut App,
@@ -47,9 +98,45 @@ fn render(
     let mut last_tick = Instant::now();
     loop {
         terminal.draw(|frame| match app.menu {
+            MenuType::MainMenu if app.mode == EditMode::ChangePreview => {
+                render_change_preview(app, frame)
+            }
             MenuType::MainMenu => render_options_menu(app, frame),
             MenuType::ItemMenu => render_item_menu(app, frame),
             MenuType::BlockMenu => render_block_menu(app, frame),
+            MenuType::CropMenu => render_crop_menu(app, frame),
+            MenuType::OreMenu => render_ore_menu(app, frame),
+            MenuType::FeatureMenu => render_feature_menu(app, frame),
+            MenuType::BiomeMenu => render_biome_menu(app, frame),
+            MenuType::DimensionMenu => render_dimension_menu(app, frame),
+            MenuType::AdvancementMenu => render_advancement_menu(app, frame),
+            MenuType::SoundMenu => render_sound_menu(app, frame),
+            MenuType::ParticleMenu => render_particle_menu(app, frame),
+            MenuType::PackMenu => render_pack_menu(app, frame),
+            MenuType::FunctionMenu => render_function_menu(app, frame),
+            MenuType::LintMenu => render_problems_menu(
+                app,
+                frame,
+                "Function Problems",
+                "to lint the functions again.",
+            ),
+            MenuType::IntegrityMenu => {
+                render_problems_menu(app, frame, "Broken References", "to check the files again.")
+            }
+            MenuType::MigrationMenu => render_problems_menu(
+                app,
+                frame,
+                "Code Using The Old Namespace",
+                "to look through the code again.",
+            ),
+            MenuType::UnusedMenu if app.mode == EditMode::ConfirmDelete => {
+                render_delete_confirmation(app, frame)
+            }
+            MenuType::UnusedMenu => render_unused_menu(app, frame),
+            MenuType::RenameMenu if app.mode == EditMode::ChangePreview => {
+                render_change_preview(app, frame)
+            }
+            MenuType::RenameMenu => render_rename_menu(app, frame),
         })?;
         let timeout = tick_rate
             .checked_sub(last_tick.elapsed())
@@ -60,13 +147,20 @@ fn render(
             if let Event::Key(key) = event::read()? {
                 // Global event handlers
                 if KeyCode::Esc == key.code {
-                    app.set_mode(EditMode::None);
-                }
-                if app.mode == EditMode::None && app.current_menu().can_navigate_back() {
-                    if key.code == KeyCode::Char('q') {
-                        app.navigate(app.menu.get_previous_menu());
+                    let migrating =
+                        matches!(app.mode, EditMode::Namespace | EditMode::ChangePreview);
+                    if app.menu == MenuType::MainMenu && migrating {
+                        app.decline_migration();
+                    } else {
+                        app.set_mode(EditMode::None);
                     }
                 }
+                if app.mode == EditMode::None
+                    && app.current_menu().can_navigate_back()
+                    && key.code == KeyCode::Char('q')
+                {
+                    app.navigate(app.menu.get_previous_menu());
+                }
                 // Screen-specific event handlers
                 match app.current_menu() {
                     MenuType::MainMenu => {
@@ -74,9 +168,13 @@ fn render(
                         match app.mode {
                             EditMode::None => {
                                 match key.code {
-                                    KeyCode::Char('e') => {
-                                        app.set_mode(EditMode::Namespace);
+                                    KeyCode::Char('e') => app.edit_namespace(),
+                                    KeyCode::Char('v') => {
+                                        app.set_mode(EditMode::Version);
                                     }
+                                    KeyCode::Char('l') => app.loader = app.loader.next(),
+                                    KeyCode::Char('u') => app.undo(),
+                                    KeyCode::Char('r') => app.redo(),
                                     KeyCode::Char('m') => {
                                         app.set_mode(EditMode::MainMenu);
                                         app.state.options().select_first()
@@ -90,23 +188,93 @@ fn render(
                                 KeyCode::Enter => {
                                     let index = app.state.options().selected();
                                     match index {
-                                        Some(val) => match val {
-                                            0 => {
-                                                app.navigate(MenuType::ItemMenu);
-                                                app.state.item_options().select_first()
-                                            }
-                                            1 => {
-                                                app.navigate(MenuType::BlockMenu);
-                                                app.state.block_options().select_first();
-                                            }
-                                            _ => (),
-                                        },
-                                        None => (),
+                                        Some(0) => {
+                                            app.navigate(MenuType::ItemMenu);
+                                            app.refresh_model_parents();
+                                            app.state.item_options().select_first();
+                                            app.state.item_text_fields().select_first();
+                                        }
+                                        Some(1) => {
+                                            app.navigate(MenuType::BlockMenu);
+                                            app.state.block_options().select_first();
+                                            app.state.block_text_fields().select_first();
+                                        }
+                                        Some(2) => {
+                                            app.navigate(MenuType::CropMenu);
+                                            app.state.crop_options().select_first();
+                                            app.state.crop_text_fields().select_first();
+                                        }
+                                        Some(3) => {
+                                            app.navigate(MenuType::OreMenu);
+                                            app.state.ore_options().select_first();
+                                            app.state.ore_text_fields().select_first();
+                                        }
+                                        Some(4) => {
+                                            app.navigate(MenuType::FeatureMenu);
+                                            app.refresh_feature_fields();
+                                            app.state.feature_options().select_first();
+                                            app.state.feature_text_fields().select_first();
+                                        }
+                                        Some(5) => {
+                                            app.navigate(MenuType::BiomeMenu);
+                                            app.state.biome_options().select_first();
+                                            app.state.biome_text_fields().select_first();
+                                        }
+                                        Some(6) => {
+                                            app.navigate(MenuType::DimensionMenu);
+                                            app.state.dimension_options().select_first();
+                                            app.state.dimension_text_fields().select_first();
+                                        }
+                                        Some(7) => {
+                                            app.navigate(MenuType::AdvancementMenu);
+                                            app.refresh_advancements();
+                                            app.state.advancement_options().select_first();
+                                            app.state.advancement_text_fields().select_first();
+                                        }
+                                        Some(8) => {
+                                            app.navigate(MenuType::SoundMenu);
+                                            app.state.sound_options().select_first();
+                                            app.state.sound_text_fields().select_first();
+                                        }
+                                        Some(9) => {
+                                            app.navigate(MenuType::ParticleMenu);
+                                            app.state.particle_options().select_first();
+                                            app.state.particle_text_fields().select_first();
+                                        }
+                                        Some(10) => {
+                                            app.navigate(MenuType::PackMenu);
+                                            app.state.pack_options().select_first();
+                                            app.state.pack_text_fields().select_first();
+                                        }
+                                        Some(11) => {
+                                            app.navigate(MenuType::FunctionMenu);
+                                            app.state.function_options().select_first();
+                                            app.state.function_text_fields().select_first();
+                                        }
+                                        Some(12) => {
+                                            app.navigate(MenuType::LintMenu);
+                                            app.lint_functions();
+                                        }
+                                        Some(13) => {
+                                            app.navigate(MenuType::IntegrityMenu);
+                                            app.check_references();
+                                        }
+                                        Some(14) => {
+                                            app.navigate(MenuType::UnusedMenu);
+                                            app.find_unused();
+                                        }
+                                        Some(15) => {
+                                            app.navigate(MenuType::RenameMenu);
+                                            app.state.rename_options().select_first();
+                                            app.state.rename_text_fields().select_first();
+                                        }
+                                        _ => (),
                                     }
                                 }
                                 _ => (),
                             },
                             EditMode::Namespace => match key.code {
+                                KeyCode::Enter => app.preview_migration(),
                                 KeyCode::Char(c) => {
                                     app.namespace.push_str(&c.to_string().to_lowercase());
                                 }
@@ -115,50 +283,315 @@ fn render(
                                 }
                                 _ => (),
                             },
+                            EditMode::Version => match key.code {
+                                KeyCode::Char(c) if c.is_ascii_digit() || c == '.' => {
+                                    app.version.push(c);
+                                }
+                                KeyCode::Backspace => {
+                                    app.version.pop();
+                                }
+                                _ => (),
+                            },
+                            EditMode::ChangePreview => match key.code {
+                                KeyCode::Down => app.state.changes().next(),
+                                KeyCode::Up => app.state.changes().previous(),
+                                KeyCode::Char('y') => app.apply_migration(),
+                                KeyCode::Char('n') => app.decline_migration(),
+                                _ => (),
+                            },
                             _ => (),
                         }
                     }
                     MenuType::ItemMenu => match app.mode {
-                        EditMode::ItemMenu => match key.code {
-                            KeyCode::Char(' ') => {
-                                let options = app.state.item_options();
-                                let index = options.selected();
-                                match index {
-                                    Some(pos) => match options.elements_mut().get_mut(pos) {
-                                        Some(item) => item.toggle(),
-                                        None => (),
-                                    },
-                                    None => (),
+                        EditMode::ItemMenu => app.state.item_options().handle_key(key.code),
+                        EditMode::ItemTextField => {
+                            app.state.item_text_fields().handle_key(key.code)
+                        }
+                        EditMode::ItemOverrides => app.state.item_overrides().handle_key(key.code),
+                        EditMode::ItemModelTree => {
+                            let tree = app.state.item_model_tree();
+                            let selected = tree.selected();
+                            match key.code {
+                                KeyCode::Enter => {
+                                    let depth = tree.selected_mut().map_or(0, |row| row.depth);
+                                    tree.insert_after_selected(TreeRow {
+                                        depth,
+                                        text: String::new(),
+                                    });
                                 }
+                                KeyCode::Tab => {
+                                    // A row can only go one level deeper than the row above it
+                                    let max = match selected {
+                                        Some(idx) if idx > 0 => tree.elements()[idx - 1].depth + 1,
+                                        _ => 0,
+                                    };
+                                    if let Some(row) = tree.selected_mut() {
+                                        row.depth = (row.depth + 1).min(max);
+                                    }
+                                }
+                                KeyCode::BackTab => {
+                                    if let Some(row) = tree.selected_mut() {
+                                        row.depth = row.depth.saturating_sub(1);
+                                    }
+                                }
+                                KeyCode::Down => tree.next(),
+                                KeyCode::Up => tree.previous(),
+                                KeyCode::Char(c) => match tree.selected_mut() {
+                                    Some(row) => row.text.push(c),
+                                    None => tree.insert_after_selected(TreeRow {
+                                        depth: 0,
+                                        text: c.to_string(),
+                                    }),
+                                },
+                                KeyCode::Backspace => match tree.selected_mut() {
+                                    Some(row) if !row.text.is_empty() => {
+                                        row.text.pop();
+                                    }
+                                    _ => tree.remove_selected(),
+                                },
+                                _ => (),
+                            }
+                        }
+                        EditMode::None => match key.code {
+                            KeyCode::Char('e') => {
+                                app.set_mode(EditMode::ItemTextField);
                             }
-                            KeyCode::Down => app.state.item_options().next(),
-                            KeyCode::Up => app.state.item_options().previous(),
+                            KeyCode::Char('t') => app.set_mode(EditMode::ItemModelTree),
+
+                            KeyCode::Char('m') => app.set_mode(EditMode::ItemMenu),
+                            KeyCode::Char('o') => app.set_mode(EditMode::ItemOverrides),
+                            KeyCode::Char('g') => app.generate_item(),
                             _ => (),
                         },
-                        EditMode::ItemIdentifier => match key.code {
-                            KeyCode::Char(c) => app
-                                .state
-                                .item_text_fields()
-                                .get(0)
-                                .data()
-                                .extend(c.to_lowercase()),
-                            KeyCode::Backspace => {
-                                app.state.item_text_fields().get(0).data().pop();
-                            }
+                        _ => (),
+                    },
+                    MenuType::BlockMenu => match app.mode {
+                        EditMode::BlockMenu => app.state.block_options().handle_key(key.code),
+                        EditMode::BlockTextField => {
+                            app.state.block_text_fields().handle_key(key.code)
+                        }
+                        EditMode::None => match key.code {
+                            KeyCode::Char('e') => app.set_mode(EditMode::BlockTextField),
+                            KeyCode::Char('m') => app.set_mode(EditMode::BlockMenu),
+                            KeyCode::Char('g') => app.generate_block(),
                             _ => (),
                         },
+                        _ => (),
+                    },
+                    MenuType::CropMenu => match app.mode {
+                        EditMode::CropMenu => app.state.crop_options().handle_key(key.code),
+                        EditMode::CropTextField => {
+                            app.state.crop_text_fields().handle_key(key.code)
+                        }
                         EditMode::None => match key.code {
-                            KeyCode::Char('e') => {
-                                app.set_mode(EditMode::ItemIdentifier);
+                            KeyCode::Char('e') => app.set_mode(EditMode::CropTextField),
+                            KeyCode::Char('m') => app.set_mode(EditMode::CropMenu),
+                            KeyCode::Char('g') => app.generate_crop(),
+                            _ => (),
+                        },
+                        _ => (),
+                    },
+                    MenuType::OreMenu => match app.mode {
+                        EditMode::OreMenu => app.state.ore_options().handle_key(key.code),
+                        EditMode::OreTextField => app.state.ore_text_fields().handle_key(key.code),
+                        EditMode::None => match key.code {
+                            KeyCode::Char('e') => app.set_mode(EditMode::OreTextField),
+                            KeyCode::Char('m') => app.set_mode(EditMode::OreMenu),
+                            KeyCode::Char('g') => app.generate_ore(),
+                            _ => (),
+                        },
+                        _ => (),
+                    },
+                    MenuType::FeatureMenu => match app.mode {
+                        EditMode::FeatureMenu => {
+                            app.state.feature_options().handle_key(key.code);
+                            app.refresh_feature_fields();
+                        }
+                        EditMode::FeatureTextField => {
+                            app.state.feature_text_fields().handle_key(key.code)
+                        }
+                        EditMode::FeaturePlacement => {
+                            app.state.feature_placement().handle_key(key.code)
+                        }
+                        EditMode::None => match key.code {
+                            KeyCode::Char('e') => app.set_mode(EditMode::FeatureTextField),
+                            KeyCode::Char('m') => app.set_mode(EditMode::FeatureMenu),
+                            KeyCode::Char('p') => app.set_mode(EditMode::FeaturePlacement),
+                            KeyCode::Char('g') => app.generate_feature(),
+                            _ => (),
+                        },
+                        _ => (),
+                    },
+                    MenuType::BiomeMenu => match app.mode {
+                        EditMode::BiomeMenu => app.state.biome_options().handle_key(key.code),
+                        EditMode::BiomeTextField => {
+                            app.state.biome_text_fields().handle_key(key.code)
+                        }
+                        EditMode::BiomeSpawns => app.state.biome_spawns().handle_key(key.code),
+                        EditMode::BiomeFeatures => app.state.biome_features().handle_key(key.code),
+                        EditMode::None => match key.code {
+                            KeyCode::Char('e') => app.set_mode(EditMode::BiomeTextField),
+                            KeyCode::Char('m') => app.set_mode(EditMode::BiomeMenu),
+                            KeyCode::Char('s') => app.set_mode(EditMode::BiomeSpawns),
+                            KeyCode::Char('f') => app.set_mode(EditMode::BiomeFeatures),
+                            KeyCode::Char('g') => app.generate_biome(),
+                            _ => (),
+                        },
+                        _ => (),
+                    },
+                    MenuType::DimensionMenu => match app.mode {
+                        EditMode::DimensionMenu => {
+                            app.state.dimension_options().handle_key(key.code)
+                        }
+                        EditMode::DimensionTextField => {
+                            app.state.dimension_text_fields().handle_key(key.code)
+                        }
+                        EditMode::None => match key.code {
+                            KeyCode::Char('e') => app.set_mode(EditMode::DimensionTextField),
+                            KeyCode::Char('m') => app.set_mode(EditMode::DimensionMenu),
+                            KeyCode::Char('g') => app.generate_dimension(),
+                            _ => (),
+                        },
+                        _ => (),
+                    },
+                    MenuType::AdvancementMenu => match app.mode {
+                        EditMode::AdvancementMenu => {
+                            app.state.advancement_options().handle_key(key.code)
+                        }
+                        EditMode::AdvancementTextField => {
+                            app.state.advancement_text_fields().handle_key(key.code)
+                        }
+                        EditMode::AdvancementCriteria => {
+                            app.state.advancement_criteria().handle_key(key.code)
+                        }
+                        EditMode::AdvancementTree => match key.code {
+                            KeyCode::Down => app.state.advancement_tree().next(),
+                            KeyCode::Up => app.state.advancement_tree().previous(),
+                            KeyCode::Enter => app.pick_advancement_parent(),
+                            _ => (),
+                        },
+                        EditMode::None => match key.code {
+                            KeyCode::Char('e') => app.set_mode(EditMode::AdvancementTextField),
+                            KeyCode::Char('m') => app.set_mode(EditMode::AdvancementMenu),
+                            KeyCode::Char('c') => app.set_mode(EditMode::AdvancementCriteria),
+                            KeyCode::Char('t') => {
+                                if !app.state.advancement_tree().elements().is_empty() {
+                                    app.state.advancement_tree().select_first();
+                                }
+                                app.set_mode(EditMode::AdvancementTree)
                             }
-
-                            KeyCode::Char('m') => app.set_mode(EditMode::ItemMenu),
+                            KeyCode::Char('g') => app.generate_advancement(),
+                            _ => (),
+                        },
+                        _ => (),
+                    },
+                    MenuType::SoundMenu => match app.mode {
+                        EditMode::SoundMenu => app.state.sound_options().handle_key(key.code),
+                        EditMode::SoundTextField => {
+                            app.state.sound_text_fields().handle_key(key.code)
+                        }
+                        EditMode::SoundFiles => app.state.sound_files().handle_key(key.code),
+                        EditMode::None => match key.code {
+                            KeyCode::Char('e') => app.set_mode(EditMode::SoundTextField),
+                            KeyCode::Char('m') => app.set_mode(EditMode::SoundMenu),
+                            KeyCode::Char('s') => app.set_mode(EditMode::SoundFiles),
+                            KeyCode::Char('g') => app.generate_sound(),
+                            _ => (),
+                        },
+                        _ => (),
+                    },
+                    MenuType::ParticleMenu => match app.mode {
+                        EditMode::ParticleMenu => app.state.particle_options().handle_key(key.code),
+                        EditMode::ParticleTextField => {
+                            app.state.particle_text_fields().handle_key(key.code)
+                        }
+                        EditMode::None => match key.code {
+                            KeyCode::Char('e') => app.set_mode(EditMode::ParticleTextField),
+                            KeyCode::Char('m') => app.set_mode(EditMode::ParticleMenu),
+                            KeyCode::Char('g') => app.generate_particle(),
+                            _ => (),
+                        },
+                        _ => (),
+                    },
+                    MenuType::PackMenu => match app.mode {
+                        EditMode::PackMenu => app.state.pack_options().handle_key(key.code),
+                        EditMode::PackTextField => {
+                            app.state.pack_text_fields().handle_key(key.code)
+                        }
+                        EditMode::None => match key.code {
+                            KeyCode::Char('e') => app.set_mode(EditMode::PackTextField),
+                            KeyCode::Char('m') => app.set_mode(EditMode::PackMenu),
+                            KeyCode::Char('g') => app.generate_pack(),
+                            KeyCode::Char('x') => app.export_pack(),
+                            _ => (),
+                        },
+                        _ => (),
+                    },
+                    MenuType::FunctionMenu => match app.mode {
+                        EditMode::FunctionMenu => app.state.function_options().handle_key(key.code),
+                        EditMode::FunctionTextField => {
+                            app.state.function_text_fields().handle_key(key.code)
+                        }
+                        EditMode::None => match key.code {
+                            KeyCode::Char('e') => app.set_mode(EditMode::FunctionTextField),
+                            KeyCode::Char('m') => app.set_mode(EditMode::FunctionMenu),
+                            KeyCode::Char('g') => app.generate_function(),
+                            _ => (),
+                        },
+                        _ => (),
+                    },
+                    MenuType::LintMenu => match key.code {
+                        KeyCode::Down => app.state.problems().next(),
+                        KeyCode::Up => app.state.problems().previous(),
+                        KeyCode::Char('r') => app.lint_functions(),
+                        _ => (),
+                    },
+                    MenuType::IntegrityMenu => match key.code {
+                        KeyCode::Down => app.state.problems().next(),
+                        KeyCode::Up => app.state.problems().previous(),
+                        KeyCode::Char('r') => app.check_references(),
+                        _ => (),
+                    },
+                    MenuType::MigrationMenu => match key.code {
+                        KeyCode::Down => app.state.problems().next(),
+                        KeyCode::Up => app.state.problems().previous(),
+                        KeyCode::Char('r') => app.find_code_references(),
+                        _ => (),
+                    },
+                    MenuType::UnusedMenu => match app.mode {
+                        EditMode::ConfirmDelete => match key.code {
+                            KeyCode::Char('y') => app.delete_unused(),
+                            KeyCode::Char('n') => app.set_mode(EditMode::None),
+                            _ => (),
+                        },
+                        _ => match key.code {
+                            KeyCode::Char('r') => app.find_unused(),
+                            KeyCode::Char('d') => app.confirm_delete(),
+                            code => app.state.unused().handle_key(code),
+                        },
+                    },
+                    MenuType::RenameMenu => match app.mode {
+                        EditMode::RenameMenu => app.state.rename_options().handle_key(key.code),
+                        EditMode::RenameTextField => {
+                            app.state.rename_text_fields().handle_key(key.code)
+                        }
+                        EditMode::ChangePreview => match key.code {
+                            KeyCode::Down => app.state.changes().next(),
+                            KeyCode::Up => app.state.changes().previous(),
+                            KeyCode::Char('y') => app.apply_rename(),
+                            KeyCode::Char('n') => app.set_mode(EditMode::None),
+                            _ => (),
+                        },
+                        EditMode::None => match key.code {
+                            KeyCode::Char('e') => app.set_mode(EditMode::RenameTextField),
+                            KeyCode::Char('m') => app.set_mode(EditMode::RenameMenu),
+                            KeyCode::Char('p') => app.preview_rename(),
                             _ => (),
                         },
                         _ => (),
                     },
-                    _ => (),
                 }
+                app.save_settings();
             }
         }
         if last_tick.elapsed() >= tick_rate {
@@ -170,23 +603,845 @@ fn render(
 
 fn render_block_menu<B: Backend>(app: &mut App, frame: &mut Frame<B>) {
     let area = Layout::default()
-        .direction(tui::layout::Direction::Horizontal)
-        .constraints([Constraint::Percentage(100)].as_ref())
+        .constraints(
+            [
+                Constraint::Length(4),
+                Constraint::Length(3),
+                Constraint::Min(4),
+                Constraint::Length(1),
+            ]
+            .as_ref(),
+        )
+        .split(frame.size());
+    let lines: Vec<Spans> = match app.mode {
+        EditMode::None => vec![
+            Spans::from(vec![
+                Span::raw("Press "),
+                Span::styled("e ", bold()),
+                Span::raw("to edit the block fields."),
+            ]),
+            menu_spans(),
+            Spans::from(vec![
+                Span::raw("Press "),
+                Span::styled("g ", bold()),
+                Span::raw("to generate the block files."),
+            ]),
+            Spans::from(vec![
+                Span::raw("Press "),
+                Span::styled("q ", bold()),
+                Span::raw("to quit the current screen."),
+            ]),
+        ],
+        EditMode::BlockMenu => vec![
+            move_menu_spans(),
+            option_editing_spans(),
+            stop_editing_spans(),
+        ],
+        EditMode::BlockTextField => vec![text_field_spans(), stop_editing_spans()],
+        _ => Vec::with_capacity(0),
+    };
+    frame.render_widget(Paragraph::new(lines), area[0]);
+    let editing = app.mode == EditMode::BlockTextField;
+    render_text_fields(frame, app.state.block_text_fields(), area[1], editing);
+    let items = app.state.block_options().list_items();
+    let list = create_menu("Block Options", items, app.mode == EditMode::BlockMenu);
+    frame.render_stateful_widget(list, area[2], app.state.block_options().current_state());
+    if let Some(status) = &app.status {
+        frame.render_widget(Paragraph::new(status.as_str()), area[3]);
+    }
+}
+
+fn render_crop_menu<B: Backend>(app: &mut App, frame: &mut Frame<B>) {
+    let area = Layout::default()
+        .constraints(
+            [
+                Constraint::Length(4),
+                Constraint::Length(3),
+                Constraint::Min(4),
+                Constraint::Length(1),
+            ]
+            .as_ref(),
+        )
+        .split(frame.size());
+    let lines: Vec<Spans> = match app.mode {
+        EditMode::None => vec![
+            key_spans("e", "to edit the crop identifier."),
+            menu_spans(),
+            key_spans("g", "to generate the crop files."),
+            key_spans("q", "to quit the current screen."),
+        ],
+        EditMode::CropMenu => vec![
+            move_menu_spans(),
+            option_editing_spans(),
+            stop_editing_spans(),
+        ],
+        EditMode::CropTextField => vec![stop_editing_spans()],
+        _ => Vec::with_capacity(0),
+    };
+    frame.render_widget(Paragraph::new(lines), area[0]);
+    let editing = app.mode == EditMode::CropTextField;
+    render_text_fields(frame, app.state.crop_text_fields(), area[1], editing);
+    let items = app.state.crop_options().list_items();
+    let list = create_menu("Crop Options", items, app.mode == EditMode::CropMenu);
+    frame.render_stateful_widget(list, area[2], app.state.crop_options().current_state());
+    if let Some(status) = &app.status {
+        frame.render_widget(Paragraph::new(status.as_str()), area[3]);
+    }
+}
+
+fn render_ore_menu<B: Backend>(app: &mut App, frame: &mut Frame<B>) {
+    let area = Layout::default()
+        .constraints(
+            [
+                Constraint::Length(5),
+                Constraint::Length(3),
+                Constraint::Min(4),
+                Constraint::Length(1),
+            ]
+            .as_ref(),
+        )
+        .split(frame.size());
+    let lines: Vec<Spans> = match app.mode {
+        EditMode::None => vec![
+            key_spans("e", "to edit the ore material."),
+            menu_spans(),
+            key_spans("g", "to generate the ore files."),
+            Spans::from(format!(
+                "The ore is added to overworld biomes the {} way, switch loaders from the main menu.",
+                app.loader
+            )),
+            key_spans("q", "to quit the current screen."),
+        ],
+        EditMode::OreMenu => vec![
+            move_menu_spans(),
+            option_editing_spans(),
+            stop_editing_spans(),
+        ],
+        EditMode::OreTextField => vec![stop_editing_spans()],
+        _ => Vec::with_capacity(0),
+    };
+    frame.render_widget(Paragraph::new(lines), area[0]);
+    let editing = app.mode == EditMode::OreTextField;
+    render_text_fields(frame, app.state.ore_text_fields(), area[1], editing);
+    let items = app.state.ore_options().list_items();
+    let list = create_menu("Ore Options", items, app.mode == EditMode::OreMenu);
+    frame.render_stateful_widget(list, area[2], app.state.ore_options().current_state());
+    if let Some(status) = &app.status {
+        frame.render_widget(Paragraph::new(status.as_str()), area[3]);
+    }
+}
+
+fn render_feature_menu<B: Backend>(app: &mut App, frame: &mut Frame<B>) {
+    let area = Layout::default()
+        .constraints(
+            [
+                Constraint::Length(5),
+                Constraint::Length(3),
+                Constraint::Min(4),
+                Constraint::Length(8),
+                Constraint::Length(1),
+            ]
+            .as_ref(),
+        )
+        .split(frame.size());
+    let lines: Vec<Spans> = match app.mode {
+        EditMode::None => vec![
+            key_spans("e", "to edit the feature identifier."),
+            menu_spans(),
+            key_spans("p", "to edit the placement modifiers."),
+            key_spans("g", "to generate the feature files."),
+            key_spans("q", "to quit the current screen."),
+        ],
+        EditMode::FeatureMenu => vec![
+            move_menu_spans(),
+            option_editing_spans(),
+            Spans::from("Changing the feature type swaps the fields below it."),
+            stop_editing_spans(),
+        ],
+        EditMode::FeatureTextField => vec![stop_editing_spans()],
+        EditMode::FeaturePlacement => vec![
+            Spans::from(format!(
+                "Rows are one of: {}.",
+                worldgen::PLACEMENT_MODIFIERS.join(", ")
+            )),
+            row_spans(),
+            move_menu_spans(),
+            stop_editing_spans(),
+        ],
+        _ => Vec::with_capacity(0),
+    };
+    frame.render_widget(Paragraph::new(lines), area[0]);
+    let editing = app.mode == EditMode::FeatureTextField;
+    render_text_fields(frame, app.state.feature_text_fields(), area[1], editing);
+    let items = app.state.feature_options().list_items();
+    let list = create_menu("Feature Options", items, app.mode == EditMode::FeatureMenu);
+    frame.render_stateful_widget(list, area[2], app.state.feature_options().current_state());
+    let rows: Vec<ListItem> = app
+        .state
+        .feature_placement()
+        .elements()
+        .iter()
+        .map(|row| ListItem::new(Text::from(row.clone())))
+        .collect();
+    let editing = app.mode == EditMode::FeaturePlacement;
+    let placement = create_menu("Placement Modifiers", rows, editing);
+    frame.render_stateful_widget(
+        placement,
+        area[3],
+        app.state.feature_placement().current_state(),
+    );
+    if let Some(status) = &app.status {
+        frame.render_widget(Paragraph::new(status.as_str()), area[4]);
+    }
+}
+
+fn render_biome_menu<B: Backend>(app: &mut App, frame: &mut Frame<B>) {
+    let area = Layout::default()
+        .constraints(
+            [
+                Constraint::Length(6),
+                Constraint::Length(3),
+                Constraint::Min(4),
+                Constraint::Length(8),
+                Constraint::Length(1),
+            ]
+            .as_ref(),
+        )
+        .split(frame.size());
+    let lines: Vec<Spans> = match app.mode {
+        EditMode::None => vec![
+            key_spans("e", "to edit the biome fields."),
+            menu_spans(),
+            key_spans("s", "to edit the mob spawns."),
+            key_spans("f", "to edit the features."),
+            key_spans("g", "to generate the biome files."),
+            key_spans("q", "to quit the current screen."),
+        ],
+        EditMode::BiomeMenu => vec![
+            move_menu_spans(),
+            option_editing_spans(),
+            stop_editing_spans(),
+        ],
+        EditMode::BiomeTextField => vec![text_field_spans(), stop_editing_spans()],
+        EditMode::BiomeSpawns => vec![
+            Spans::from("Rows are written as 'category entity weight min max'."),
+            Spans::from(format!(
+                "Categories are {}.",
+                generator::biome::SPAWN_CATEGORIES.join(", ")
+            )),
+            row_spans(),
+            move_menu_spans(),
+            stop_editing_spans(),
+        ],
+        EditMode::BiomeFeatures => vec![
+            Spans::from("Rows are written as 'step placed_feature'."),
+            Spans::from(format!("Steps are {}.", generator::biome::STEPS.join(", "))),
+            row_spans(),
+            move_menu_spans(),
+            stop_editing_spans(),
+        ],
+        _ => Vec::with_capacity(0),
+    };
+    frame.render_widget(Paragraph::new(lines).wrap(Wrap { trim: true }), area[0]);
+    let editing = app.mode == EditMode::BiomeTextField;
+    render_text_fields(frame, app.state.biome_text_fields(), area[1], editing);
+    let items = app.state.biome_options().list_items();
+    let list = create_menu("Biome Options", items, app.mode == EditMode::BiomeMenu);
+    frame.render_stateful_widget(list, area[2], app.state.biome_options().current_state());
+    let panels = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)].as_ref())
+        .split(area[3]);
+    let rows: Vec<ListItem> = app
+        .state
+        .biome_spawns()
+        .elements()
+        .iter()
+        .map(|row| ListItem::new(Text::from(row.clone())))
+        .collect();
+    let spawns = create_menu("Mob Spawns", rows, app.mode == EditMode::BiomeSpawns);
+    frame.render_stateful_widget(spawns, panels[0], app.state.biome_spawns().current_state());
+    let rows: Vec<ListItem> = app
+        .state
+        .biome_features()
+        .elements()
+        .iter()
+        .map(|row| ListItem::new(Text::from(row.clone())))
+        .collect();
+    let features = create_menu("Features", rows, app.mode == EditMode::BiomeFeatures);
+    frame.render_stateful_widget(
+        features,
+        panels[1],
+        app.state.biome_features().current_state(),
+    );
+    if let Some(status) = &app.status {
+        frame.render_widget(Paragraph::new(status.as_str()), area[4]);
+    }
+}
+
+fn render_dimension_menu<B: Backend>(app: &mut App, frame: &mut Frame<B>) {
+    let area = Layout::default()
+        .constraints(
+            [
+                Constraint::Length(4),
+                Constraint::Length(3),
+                Constraint::Min(4),
+                Constraint::Length(1),
+            ]
+            .as_ref(),
+        )
+        .split(frame.size());
+    let lines: Vec<Spans> = match app.mode {
+        EditMode::None => vec![
+            key_spans("e", "to edit the dimension identifier."),
+            menu_spans(),
+            key_spans("g", "to generate the dimension files."),
+            key_spans("q", "to quit the current screen."),
+        ],
+        EditMode::DimensionMenu => vec![
+            move_menu_spans(),
+            option_editing_spans(),
+            stop_editing_spans(),
+        ],
+        EditMode::DimensionTextField => vec![stop_editing_spans()],
+        _ => Vec::with_capacity(0),
+    };
+    frame.render_widget(Paragraph::new(lines), area[0]);
+    let editing = app.mode == EditMode::DimensionTextField;
+    render_text_fields(frame, app.state.dimension_text_fields(), area[1], editing);
+    let items = app.state.dimension_options().list_items();
+    let list = create_menu(
+        "Dimension Options",
+        items,
+        app.mode == EditMode::DimensionMenu,
+    );
+    frame.render_stateful_widget(list, area[2], app.state.dimension_options().current_state());
+    if let Some(status) = &app.status {
+        frame.render_widget(Paragraph::new(status.as_str()), area[3]);
+    }
+}
+
+fn render_advancement_menu<B: Backend>(app: &mut App, frame: &mut Frame<B>) {
+    let area = Layout::default()
+        .constraints(
+            [
+                Constraint::Length(6),
+                Constraint::Length(3),
+                Constraint::Min(4),
+                Constraint::Length(10),
+                Constraint::Length(1),
+            ]
+            .as_ref(),
+        )
+        .split(frame.size());
+    let lines: Vec<Spans> = match app.mode {
+        EditMode::None => vec![
+            key_spans("e", "to edit the advancement fields."),
+            menu_spans(),
+            key_spans("c", "to edit the criteria."),
+            key_spans("t", "to browse the advancement tree."),
+            key_spans("g", "to generate the advancement."),
+            key_spans("q", "to quit the current screen."),
+        ],
+        EditMode::AdvancementMenu => vec![
+            move_menu_spans(),
+            option_editing_spans(),
+            stop_editing_spans(),
+        ],
+        EditMode::AdvancementTextField => vec![text_field_spans(), stop_editing_spans()],
+        EditMode::AdvancementCriteria => vec![
+            Spans::from("Rows are written as 'name trigger [argument]', the triggers being:"),
+            Spans::from(generator::advancement::TRIGGERS.join(", ")),
+            row_spans(),
+            move_menu_spans(),
+            stop_editing_spans(),
+        ],
+        EditMode::AdvancementTree => vec![
+            move_menu_spans(),
+            key_spans("Enter", "to make the selected advancement the parent."),
+            stop_editing_spans(),
+        ],
+        _ => Vec::with_capacity(0),
+    };
+    frame.render_widget(Paragraph::new(lines).wrap(Wrap { trim: true }), area[0]);
+    let editing = app.mode == EditMode::AdvancementTextField;
+    render_text_fields(frame, app.state.advancement_text_fields(), area[1], editing);
+    let items = app.state.advancement_options().list_items();
+    let editing = app.mode == EditMode::AdvancementMenu;
+    let list = create_menu("Advancement Options", items, editing);
+    frame.render_stateful_widget(
+        list,
+        area[2],
+        app.state.advancement_options().current_state(),
+    );
+    let panels = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)].as_ref())
+        .split(area[3]);
+    let rows: Vec<ListItem> = app
+        .state
+        .advancement_criteria()
+        .elements()
+        .iter()
+        .map(|row| ListItem::new(Text::from(row.clone())))
+        .collect();
+    let editing = app.mode == EditMode::AdvancementCriteria;
+    let criteria = create_menu("Criteria", rows, editing);
+    frame.render_stateful_widget(
+        criteria,
+        panels[0],
+        app.state.advancement_criteria().current_state(),
+    );
+    let rows: Vec<ListItem> = app
+        .state
+        .advancement_tree()
+        .elements()
+        .iter()
+        .map(|row| {
+            let prefix = match row.depth {
+                0 => String::new(),
+                depth => format!("{}\u{2514} ", "  ".repeat(depth - 1)),
+            };
+            ListItem::new(Text::from(format!("{}{}", prefix, row.text)))
+        })
+        .collect();
+    let editing = app.mode == EditMode::AdvancementTree;
+    let tree = create_menu("Advancement Tree", rows, editing);
+    frame.render_stateful_widget(
+        tree,
+        panels[1],
+        app.state.advancement_tree().current_state(),
+    );
+    if let Some(status) = &app.status {
+        frame.render_widget(Paragraph::new(status.as_str()), area[4]);
+    }
+}
+
+fn render_sound_menu<B: Backend>(app: &mut App, frame: &mut Frame<B>) {
+    let area = Layout::default()
+        .constraints(
+            [
+                Constraint::Length(6),
+                Constraint::Length(3),
+                Constraint::Length(5),
+                Constraint::Min(4),
+                Constraint::Length(1),
+            ]
+            .as_ref(),
+        )
+        .split(frame.size());
+    let lines: Vec<Spans> = match app.mode {
+        EditMode::None => vec![
+            key_spans("e", "to edit the identifier and subtitle."),
+            menu_spans(),
+            key_spans("s", "to edit the sound files."),
+            key_spans("g", "to generate the sound event."),
+            key_spans("q", "to quit the current screen."),
+        ],
+        EditMode::SoundMenu => vec![
+            move_menu_spans(),
+            option_editing_spans(),
+            stop_editing_spans(),
+        ],
+        EditMode::SoundTextField => vec![text_field_spans(), stop_editing_spans()],
+        EditMode::SoundFiles => vec![
+            Spans::from("Rows are written as 'path [volume] [pitch] [weight] [stream]'."),
+            Spans::from("Paths are relative to the sounds folder, like 'block/chime1'."),
+            row_spans(),
+            move_menu_spans(),
+            stop_editing_spans(),
+        ],
+        _ => Vec::with_capacity(0),
+    };
+    frame.render_widget(Paragraph::new(lines).wrap(Wrap { trim: true }), area[0]);
+    let editing = app.mode == EditMode::SoundTextField;
+    render_text_fields(frame, app.state.sound_text_fields(), area[1], editing);
+    let items = app.state.sound_options().list_items();
+    let list = create_menu("Sound Options", items, app.mode == EditMode::SoundMenu);
+    frame.render_stateful_widget(list, area[2], app.state.sound_options().current_state());
+    let rows: Vec<ListItem> = app
+        .state
+        .sound_files()
+        .elements()
+        .iter()
+        .map(|row| ListItem::new(Text::from(row.clone())))
+        .collect();
+    let sounds = create_menu("Sound Files", rows, app.mode == EditMode::SoundFiles);
+    frame.render_stateful_widget(sounds, area[3], app.state.sound_files().current_state());
+    if let Some(status) = &app.status {
+        frame.render_widget(Paragraph::new(status.as_str()), area[4]);
+    }
+}
+
+fn render_particle_menu<B: Backend>(app: &mut App, frame: &mut Frame<B>) {
+    let area = Layout::default()
+        .constraints(
+            [
+                Constraint::Length(4),
+                Constraint::Length(3),
+                Constraint::Min(4),
+                Constraint::Length(1),
+            ]
+            .as_ref(),
+        )
+        .split(frame.size());
+    let lines: Vec<Spans> = match app.mode {
+        EditMode::None => vec![
+            key_spans("e", "to edit the particle identifier."),
+            menu_spans(),
+            key_spans("g", "to generate the particle files."),
+            key_spans("q", "to quit the current screen."),
+        ],
+        EditMode::ParticleMenu => vec![
+            move_menu_spans(),
+            option_editing_spans(),
+            stop_editing_spans(),
+        ],
+        EditMode::ParticleTextField => vec![stop_editing_spans()],
+        _ => Vec::with_capacity(0),
+    };
+    frame.render_widget(Paragraph::new(lines), area[0]);
+    let editing = app.mode == EditMode::ParticleTextField;
+    render_text_fields(frame, app.state.particle_text_fields(), area[1], editing);
+    let items = app.state.particle_options().list_items();
+    let list = create_menu(
+        "Particle Options",
+        items,
+        app.mode == EditMode::ParticleMenu,
+    );
+    frame.render_stateful_widget(list, area[2], app.state.particle_options().current_state());
+    if let Some(status) = &app.status {
+        frame.render_widget(Paragraph::new(status.as_str()), area[3]);
+    }
+}
+
+fn render_pack_menu<B: Backend>(app: &mut App, frame: &mut Frame<B>) {
+    let area = Layout::default()
+        .constraints(
+            [
+                Constraint::Length(6),
+                Constraint::Length(3),
+                Constraint::Min(4),
+                Constraint::Length(1),
+            ]
+            .as_ref(),
+        )
+        .split(frame.size());
+    let lines: Vec<Spans> = match app.mode {
+        EditMode::None => vec![
+            key_spans(
+                "e",
+                "to edit the folder and description, an empty folder is named after the namespace.",
+            ),
+            menu_spans(),
+            key_spans("g", "to create the pack."),
+            key_spans(
+                "x",
+                "to export the pack you write into as a zip next to it.",
+            ),
+            key_spans("q", "to quit the current screen."),
+        ],
+        EditMode::PackMenu => vec![
+            move_menu_spans(),
+            option_editing_spans(),
+            stop_editing_spans(),
+        ],
+        EditMode::PackTextField => vec![text_field_spans(), stop_editing_spans()],
+        _ => Vec::with_capacity(0),
+    };
+    frame.render_widget(Paragraph::new(lines).wrap(Wrap { trim: true }), area[0]);
+    let editing = app.mode == EditMode::PackTextField;
+    render_text_fields(frame, app.state.pack_text_fields(), area[1], editing);
+    let items = app.state.pack_options().list_items();
+    let list = create_menu("Pack Options", items, app.mode == EditMode::PackMenu);
+    frame.render_stateful_widget(list, area[2], app.state.pack_options().current_state());
+    if let Some(status) = &app.status {
+        frame.render_widget(Paragraph::new(status.as_str()), area[3]);
+    }
+}
+
+fn render_function_menu<B: Backend>(app: &mut App, frame: &mut Frame<B>) {
+    let area = Layout::default()
+        .constraints(
+            [
+                Constraint::Length(4),
+                Constraint::Length(3),
+                Constraint::Min(4),
+                Constraint::Length(1),
+            ]
+            .as_ref(),
+        )
         .split(frame.size());
-    let items = vec![
-        ListItem::new(Text::from("[]")),
-        ListItem::new(Text::from("[]")),
+    let lines: Vec<Spans> = match app.mode {
+        EditMode::None => vec![
+            key_spans("e", "to edit the function path, such as gameplay/init."),
+            menu_spans(),
+            key_spans("g", "to create the function."),
+            key_spans("q", "to quit the current screen."),
+        ],
+        EditMode::FunctionMenu => vec![
+            move_menu_spans(),
+            option_editing_spans(),
+            stop_editing_spans(),
+        ],
+        EditMode::FunctionTextField => vec![stop_editing_spans()],
+        _ => Vec::with_capacity(0),
+    };
+    frame.render_widget(Paragraph::new(lines), area[0]);
+    let editing = app.mode == EditMode::FunctionTextField;
+    render_text_fields(frame, app.state.function_text_fields(), area[1], editing);
+    let items = app.state.function_options().list_items();
+    let list = create_menu(
+        "Function Options",
+        items,
+        app.mode == EditMode::FunctionMenu,
+    );
+    frame.render_stateful_widget(list, area[2], app.state.function_options().current_state());
+    if let Some(status) = &app.status {
+        frame.render_widget(Paragraph::new(status.as_str()), area[3]);
+    }
+}
+
+fn render_rename_menu<B: Backend>(app: &mut App, frame: &mut Frame<B>) {
+    let area = Layout::default()
+        .constraints(
+            [
+                Constraint::Length(4),
+                Constraint::Length(3),
+                Constraint::Min(4),
+                Constraint::Length(1),
+            ]
+            .as_ref(),
+        )
+        .split(frame.size());
+    let lines: Vec<Spans> = match app.mode {
+        EditMode::None => vec![
+            key_spans("e", "to edit the id to rename and its new name."),
+            menu_spans(),
+            key_spans("p", "to preview every change before renaming."),
+            key_spans("q", "to quit the current screen."),
+        ],
+        EditMode::RenameMenu => vec![
+            move_menu_spans(),
+            option_editing_spans(),
+            stop_editing_spans(),
+        ],
+        EditMode::RenameTextField => vec![stop_editing_spans()],
+        _ => Vec::with_capacity(0),
+    };
+    frame.render_widget(Paragraph::new(lines), area[0]);
+    let editing = app.mode == EditMode::RenameTextField;
+    render_text_fields(frame, app.state.rename_text_fields(), area[1], editing);
+    let items = app.state.rename_options().list_items();
+    let list = create_menu("Rename Options", items, app.mode == EditMode::RenameMenu);
+    frame.render_stateful_widget(list, area[2], app.state.rename_options().current_state());
+    if let Some(status) = &app.status {
+        frame.render_widget(Paragraph::new(status.as_str()), area[3]);
+    }
+}
+
+/// Lists every change a refactor is about to make, for it to be applied or
+/// dropped.
+fn render_change_preview<B: Backend>(app: &mut App, frame: &mut Frame<B>) {
+    let area = Layout::default()
+        .constraints(
+            [
+                Constraint::Length(3),
+                Constraint::Min(4),
+                Constraint::Length(1),
+            ]
+            .as_ref(),
+        )
+        .split(frame.size());
+    let lines = vec![
+        move_menu_spans(),
+        key_spans("y", "to make these changes."),
+        key_spans("n", "or Esc to go back without changing anything."),
+    ];
+    frame.render_widget(Paragraph::new(lines), area[0]);
+    let root = app.root.clone();
+    let items: Vec<ListItem> = app
+        .state
+        .changes()
+        .elements()
+        .iter()
+        .map(|change: &Change| ListItem::new(change.describe(&root)))
+        .collect();
+    let list = create_menu("Changes", items, true);
+    frame.render_stateful_widget(list, area[1], app.state.changes().current_state());
+    if let Some(status) = &app.status {
+        frame.render_widget(Paragraph::new(status.as_str()), area[2]);
+    }
+}
+
+/// Renders the problem list shared by the lint and check screens, `rescan`
+/// telling what `r` does.
+fn render_problems_menu<B: Backend>(
+    app: &mut App,
+    frame: &mut Frame<B>,
+    title: &'static str,
+    rescan: &'static str,
+) {
+    let area = Layout::default()
+        .constraints(
+            [
+                Constraint::Length(3),
+                Constraint::Min(4),
+                Constraint::Length(1),
+            ]
+            .as_ref(),
+        )
+        .split(frame.size());
+    let lines = vec![
+        move_menu_spans(),
+        key_spans("r", rescan),
+        key_spans("q", "to quit the current screen."),
+    ];
+    frame.render_widget(Paragraph::new(lines), area[0]);
+    let root = app.root.clone();
+    let problems = problem_items(app.state.problems(), &root);
+    let list = create_menu(title, problems, true);
+    frame.render_stateful_widget(list, area[1], app.state.problems().current_state());
+    if let Some(status) = &app.status {
+        frame.render_widget(Paragraph::new(status.as_str()), area[2]);
+    }
+}
+
+fn render_unused_menu<B: Backend>(app: &mut App, frame: &mut Frame<B>) {
+    let area = Layout::default()
+        .constraints(
+            [
+                Constraint::Length(5),
+                Constraint::Min(4),
+                Constraint::Length(1),
+            ]
+            .as_ref(),
+        )
+        .split(frame.size());
+    let lines = vec![
+        move_menu_spans(),
+        Spans::from(vec![
+            Span::styled("Space", bold()),
+            Span::raw(" to pick an asset, "),
+            Span::styled("a", bold()),
+            Span::raw(" to pick them all."),
+        ]),
+        key_spans("d", "to delete the picked assets."),
+        key_spans("r", "to look for unused assets again."),
+        key_spans("q", "to quit the current screen."),
     ];
-    let list = List::new(items);
-    frame.render_stateful_widget(list, area[0], app.state.block_options().current_state());
+    frame.render_widget(Paragraph::new(lines), area[0]);
+    let root = app.root.clone();
+    let items = unused_items(app.state.unused().elements().iter(), &root, true);
+    let list = create_menu("Unused Assets", items, true);
+    frame.render_stateful_widget(list, area[1], app.state.unused().current_state());
+    if let Some(status) = &app.status {
+        frame.render_widget(Paragraph::new(status.as_str()), area[2]);
+    }
+}
+
+/// The screen standing between picking unused assets and deleting them.
+fn render_delete_confirmation<B: Backend>(app: &mut App, frame: &mut Frame<B>) {
+    let area = Layout::default()
+        .constraints([Constraint::Length(3), Constraint::Min(4)].as_ref())
+        .split(frame.size());
+    let root = app.root.clone();
+    let picked = app.state.unused().picked();
+    let lines = vec![
+        Spans::from(Span::styled(
+            format!(
+                "Delete these {} asset(s)? Press u on the main menu to undo the cleanup.",
+                picked.len()
+            ),
+            bold(),
+        )),
+        key_spans("y", "to delete them."),
+        key_spans("n", "or Esc to go back without deleting anything."),
+    ];
+    frame.render_widget(Paragraph::new(lines), area[0]);
+    let items = unused_items(picked.into_iter(), &root, false);
+    frame.render_widget(create_menu("To Delete", items, false), area[1]);
+}
+
+/// Lists unused assets as their name over their file, with whether they are
+/// picked when `checkboxes`.
+fn unused_items<'a, 'b>(
+    unused: impl Iterator<Item = &'b Unused>,
+    root: &Path,
+    checkboxes: bool,
+) -> Vec<ListItem<'a>> {
+    unused
+        .map(|entry| {
+            let name = match (checkboxes, entry.selected) {
+                (false, _) => entry.name.clone(),
+                (true, true) => format!("[x] {}", entry.name),
+                (true, false) => format!("[ ] {}", entry.name),
+            };
+            let path = entry.path.strip_prefix(root).unwrap_or(&entry.path);
+            ListItem::new(vec![
+                Spans::from(Span::styled(name, bold())),
+                Spans::from(Span::styled(path.display().to_string(), italic())),
+            ])
+        })
+        .collect()
+}
+
+/// Lists problems as their location over their message.
+fn problem_items<'a>(problems: &StatefulList<Problem>, root: &Path) -> Vec<ListItem<'a>> {
+    problems
+        .elements()
+        .iter()
+        .map(|problem| {
+            ListItem::new(vec![
+                Spans::from(Span::styled(problem.location(root), bold())),
+                Spans::from(Span::styled(problem.message.clone(), italic())),
+            ])
+        })
+        .collect()
+}
+
+/// Renders the fields side by side, placing the cursor in the selected one
+/// while `editing`.
+fn render_text_fields<B: Backend>(
+    frame: &mut Frame<B>,
+    fields: &StatefulList<TextFieldState>,
+    area: Rect,
+    editing: bool,
+) {
+    let count = fields.elements().len() as u32;
+    let constraints: Vec<Constraint> = (0..count).map(|_| Constraint::Ratio(1, count)).collect();
+    let field_areas = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints(constraints)
+        .split(area);
+    let selected = fields.selected();
+    for (idx, (field, field_area)) in fields.elements().iter().zip(field_areas).enumerate() {
+        let active = editing && selected == Some(idx);
+        let mut input = String::from(field.value());
+        input.insert_str(0, " > ");
+        frame.render_widget(
+            text_field(Paragraph::new(input), field.title(), active),
+            field_area,
+        );
+        if active {
+            // Make the cursor visible and ask tui-rs to put it at the specified coordinates after rendering
+            frame.set_cursor(
+                // Put cursor past the end of the input text
+                field_area.x + field.value().width() as u16 + 4, // symbol takes 3 spaces + 1 offset
+                // Move one line down, from the border to the input line
+                field_area.y + 1,
+            )
+        }
+    }
 }
 
 fn render_options_menu<B: Backend>(app: &mut App, frame: &mut Frame<B>) {
     // Define constrains for widgets
     let constrains = [
-        Constraint::Length(2),
+        Constraint::Length(5),
         Constraint::Length(3),
-        Constraint::Percentage(80),
+        Constraint::Min(4),
+        Constraint::Length(1),
     ];
     // Define area
     let area = Layout::default()
@@ -203,12 +1458,34 @@ fn render_options_menu<B: Backend>(app: &mut App, frame: &mut Frame<B>) {
                 Span::styled("e ", bold_style),
                 Span::raw("to edit your namespace."),
             ];
+            let second_line = vec![
+                Span::raw("Press "),
+                Span::styled("v ", bold_style),
+                Span::raw("to edit the target Minecraft version."),
+            ];
             // Map to Spans which holds a vector of span
-            vec![Spans::from(first_line), menu_spans()]
-        }
-        EditMode::Namespace => {
-            vec![stop_editing_spans()]
+            vec![
+                Spans::from(first_line),
+                Spans::from(second_line),
+                key_spans("l", "to switch the mod loader."),
+                Spans::from(vec![
+                    Span::raw("Press "),
+                    Span::styled("u ", bold_style),
+                    Span::raw("to undo the last generation, "),
+                    Span::styled("r ", bold_style),
+                    Span::raw("to redo it."),
+                ]),
+                menu_spans(),
+            ]
         }
+        EditMode::Namespace => vec![
+            key_spans(
+                "Enter",
+                "to move the files of the old namespace to this one.",
+            ),
+            stop_editing_spans(),
+        ],
+        EditMode::Version => vec![stop_editing_spans()],
         EditMode::MainMenu => {
             vec![move_menu_spans(), stop_editing_spans()]
         }
@@ -220,24 +1497,38 @@ fn render_options_menu<B: Backend>(app: &mut App, frame: &mut Frame<B>) {
     let paragraph = Paragraph::new(text);
     // Render text
     frame.render_widget(paragraph, area[0]);
-    // Create input text field
-    let mut output = String::from(app.namespace.as_str());
-    output.insert_str(0, " > ");
-    let text_widget = text_field(
-        &app.mode,
-        EditMode::Namespace,
-        Paragraph::new(output),
-        "Namespace",
-    );
-    frame.render_widget(text_widget, area[1]);
-    if app.mode == EditMode::Namespace {
-        // Make the cursor visible and ask tui-rs to put it at the specified coordinates after rendering
-        frame.set_cursor(
-            // Put cursor past the end of the input text
-            area[1].x + app.namespace.width() as u16 + 4, // symbol takes 3 spaces + 1 offset
-            // Move one line down, from the border to the input line
-            area[1].y + 1,
+    // Create input text fields
+    let fields = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints(
+            [
+                Constraint::Percentage(40),
+                Constraint::Percentage(30),
+                Constraint::Percentage(30),
+            ]
+            .as_ref(),
         )
+        .split(area[1]);
+    let inputs = [
+        (app.namespace.as_str(), "Namespace", EditMode::Namespace),
+        (app.version.as_str(), "Target version", EditMode::Version),
+    ];
+    let loader = Paragraph::new(format!(" > {}", app.loader));
+    frame.render_widget(text_field(loader, "Mod loader", false), fields[2]);
+    for ((input, title, mode), field_area) in inputs.into_iter().zip(fields) {
+        let mut output = String::from(input);
+        output.insert_str(0, " > ");
+        let text_widget = text_field(Paragraph::new(output), title, app.mode == mode);
+        frame.render_widget(text_widget, field_area);
+        if app.mode == mode {
+            // Make the cursor visible and ask tui-rs to put it at the specified coordinates after rendering
+            frame.set_cursor(
+                // Put cursor past the end of the input text
+                field_area.x + input.width() as u16 + 4, // symbol takes 3 spaces + 1 offset
+                // Move one line down, from the border to the input line
+                field_area.y + 1,
+            )
+        }
     }
     // Render menu
     let items: Vec<ListItem> = app
@@ -250,25 +1541,26 @@ fn render_options_menu<B: Backend>(app: &mut App, frame: &mut Frame<B>) {
                 Spans::from(entry.get_option()),
                 Spans::from(Span::styled(entry.get_desc(), italic())),
             ];
-            return ListItem::new(lines).style(Style::default().fg(Color::White));
+            ListItem::new(lines).style(Style::default().fg(Color::White))
         })
         .collect();
 
     let menu_widget = create_menu("Select an option", items, app.mode == EditMode::MainMenu);
-    frame.render_stateful_widget(
-        menu_widget,
-        area[2],
-        &mut app.state.options().current_state(),
-    )
+    frame.render_stateful_widget(menu_widget, area[2], app.state.options().current_state());
+    if let Some(status) = &app.status {
+        frame.render_widget(Paragraph::new(status.as_str()), area[3]);
+    }
 }
 
 fn render_item_menu<B: Backend>(app: &mut App, frame: &mut Frame<B>) {
     let area = Layout::default()
         .constraints(
             [
+                Constraint::Length(6),
                 Constraint::Length(3),
-                Constraint::Length(3),
-                Constraint::Percentage(70),
+                Constraint::Min(4),
+                Constraint::Length(8),
+                Constraint::Length(1),
             ]
             .as_ref(),
         )
@@ -280,59 +1572,104 @@ fn render_item_menu<B: Backend>(app: &mut App, frame: &mut Frame<B>) {
             Spans::from(vec![
                 Span::raw("Press "),
                 Span::styled("e ", bold()),
-                Span::raw("to edit the item name."),
+                Span::raw("to edit the item fields."),
             ]),
             menu_spans(),
+            Spans::from(vec![
+                Span::raw("Press "),
+                Span::styled("o ", bold()),
+                Span::raw("to edit the model overrides."),
+            ]),
+            Spans::from(vec![
+                Span::raw("Press "),
+                Span::styled("t ", bold()),
+                Span::raw("to edit the item model tree (1.21.4 and later)."),
+            ]),
+            Spans::from(vec![
+                Span::raw("Press "),
+                Span::styled("g ", bold()),
+                Span::raw("to generate the item files."),
+            ]),
             Spans::from(vec![
                 Span::raw("Press "),
                 Span::styled("q ", bold()),
                 Span::raw("to quit the current screen."),
             ]),
         ],
-        EditMode::ItemMenu => vec![move_menu_spans(), stop_editing_spans()],
-        EditMode::ItemIdentifier => vec![stop_editing_spans()],
+        EditMode::ItemMenu => vec![
+            move_menu_spans(),
+            option_editing_spans(),
+            stop_editing_spans(),
+        ],
+        EditMode::ItemTextField => vec![text_field_spans(), stop_editing_spans()],
+        EditMode::ItemOverrides => vec![
+            Spans::from("Rows are written as 'pull=0.65 pulling=1 -> model [texture]'."),
+            row_spans(),
+            move_menu_spans(),
+            stop_editing_spans(),
+        ],
+        EditMode::ItemModelTree => vec![
+            Spans::from(
+                "Rows are written as '[key: ]model|condition|select|range_dispatch|empty args'.",
+            ),
+            Spans::from(
+                "Keys are true/false under condition, cases or thresholds and fallback otherwise.",
+            ),
+            Spans::from(vec![
+                Span::raw("Press "),
+                Span::styled("Tab ", bold()),
+                Span::raw("or "),
+                Span::styled("Shift+Tab ", bold()),
+                Span::raw("to nest a row, "),
+                Span::styled("Enter ", bold()),
+                Span::raw("to add one."),
+            ]),
+            move_menu_spans(),
+            stop_editing_spans(),
+        ],
         _ => Vec::with_capacity(0),
     };
     frame.render_widget(Paragraph::new(lines), area[0]);
-    let items: Vec<ListItem> = app
+    let items = app.state.item_options().list_items();
+    let editing = app.mode == EditMode::ItemTextField;
+    render_text_fields(frame, app.state.item_text_fields(), area[1], editing);
+    let list = create_menu("Item Options", items, app.mode == EditMode::ItemMenu);
+    frame.render_stateful_widget(list, area[2], app.state.item_options().current_state());
+    let rows: Vec<ListItem> = app
         .state
-        .item_options()
+        .item_overrides()
         .elements()
         .iter()
-        .map(|element| {
-            let line = format!(
-                "[{}] {}:  {}",
-                if element.is_active() { 'x' } else { ' ' },
-                element.get_option(),
-                element.get_desc()
-            );
-            ListItem::new(Text::from(line))
-        })
+        .map(|row| ListItem::new(Text::from(row.clone())))
         .collect();
-    // TextFields render
-    let text_fields = app.state.item_text_fields();
-    let mut id = String::from(text_fields.get(0).data().as_str());
-    id.insert_str(0, " > ");
-    let text_field = text_field(
-        &app.mode,
-        EditMode::ItemIdentifier,
-        Paragraph::new(id),
-        text_fields.get(0).title(),
+    let overrides = create_menu("Model Overrides", rows, app.mode == EditMode::ItemOverrides);
+    let panels = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)].as_ref())
+        .split(area[3]);
+    frame.render_stateful_widget(
+        overrides,
+        panels[0],
+        app.state.item_overrides().current_state(),
     );
-    frame.render_widget(text_field, area[1]);
-
-    if app.mode == EditMode::ItemIdentifier {
-        // Make the cursor visible and ask tui-rs to put it at the specified coordinates after rendering
-        frame.set_cursor(
-            // Put cursor past the end of the input text
-            area[1].x + text_fields.get(0).data().width() as u16 + 4, // symbol takes 3 spaces + 1 offset
-            // Move one line down, from the border to the input line
-            area[1].y + 1,
-        )
+    let rows: Vec<ListItem> = app
+        .state
+        .item_model_tree()
+        .elements()
+        .iter()
+        .map(|row| {
+            ListItem::new(Text::from(format!(
+                "{}{}",
+                "  ".repeat(row.depth),
+                row.text
+            )))
+        })
+        .collect();
+    let tree = create_menu("Item Model Tree", rows, app.mode == EditMode::ItemModelTree);
+    frame.render_stateful_widget(tree, panels[1], app.state.item_model_tree().current_state());
+    if let Some(status) = &app.status {
+        frame.render_widget(Paragraph::new(status.as_str()), area[4]);
     }
-    if app.mode == EditMode::ItemDisplayName {}
-    let list = create_menu("Item Options", items, app.mode == EditMode::ItemMenu);
-    frame.render_stateful_widget(list, area[2], app.state.item_options().current_state());
 }
 // pub fn create_menu<'a>(title: &'a str, entries: Vec<ListItem<'a>>, active: bool) -> List<'a> {
 //     let mut menu = List::new(entries).block(Block::default().borders(Borders::ALL).title(title));
@@ -0,0 +1,196 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use serde_json::{Map, Value};
+
+use crate::{journal::Journal, loader::Loader, version::GameVersion};
+
+/// The mod project generated files are written into.
+pub struct Project {
+    root: PathBuf,
+    resources: PathBuf,
+    namespace: String,
+    version: GameVersion,
+    loader: Loader,
+    journal: Journal,
+}
+
+impl Project {
+    /// Creates a project rooted at `root`. Files go to `src/main/resources`
+    /// when the project has one, otherwise straight into `root`.
+    pub fn new(root: &Path, namespace: &str, version: GameVersion, loader: Loader) -> Self {
+        let resources = root.join("src").join("main").join("resources");
+        Self {
+            root: root.to_path_buf(),
+            resources: if resources.is_dir() {
+                resources
+            } else {
+                root.to_path_buf()
+            },
+            namespace: namespace.to_string(),
+            version,
+            loader,
+            journal: Journal::new(root),
+        }
+    }
+    /// The folder the project was opened at.
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+    pub fn namespace(&self) -> &str {
+        &self.namespace
+    }
+    pub fn version(&self) -> GameVersion {
+        self.version
+    }
+    pub fn loader(&self) -> Loader {
+        self.loader
+    }
+    /// The folder holding `assets` and `data`.
+    pub fn resources(&self) -> &Path {
+        &self.resources
+    }
+    /// `assets/<namespace>` inside the resources folder.
+    pub fn assets(&self) -> PathBuf {
+        self.resources.join("assets").join(&self.namespace)
+    }
+    /// `data/<namespace>` inside the resources folder.
+    pub fn data(&self) -> PathBuf {
+        self.data_of(&self.namespace)
+    }
+    pub fn data_of(&self, namespace: &str) -> PathBuf {
+        self.resources.join("data").join(namespace)
+    }
+    /// The `kind` folder of the namespace's data, named for the target
+    /// version, e.g. `data/<namespace>/loot_tables` before 1.21.
+    pub fn data_folder(&self, kind: &str) -> PathBuf {
+        self.data().join(self.version.data_folder(kind))
+    }
+    /// Turns `path` into a `<namespace>:path` resource location.
+    pub fn resource(&self, path: &str) -> String {
+        format!("{}:{}", self.namespace, path)
+    }
+    pub fn write(&self, path: &Path, contents: &str) -> io::Result<PathBuf> {
+        self.write_bytes(path, contents.as_bytes())
+    }
+    pub fn write_bytes(&self, path: &Path, contents: &[u8]) -> io::Result<PathBuf> {
+        self.journal.record(path)?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, contents)?;
+        Ok(path.to_path_buf())
+    }
+    /// Deletes the file at `path`, along with the folders that leaves empty.
+    pub fn remove(&self, path: &Path) -> io::Result<()> {
+        self.journal.record(path)?;
+        fs::remove_file(path)?;
+        remove_empty_parents(path, &self.resources);
+        Ok(())
+    }
+    /// Moves the file at `from` to `to`, creating the folders it needs.
+    pub fn rename(&self, from: &Path, to: &Path) -> io::Result<()> {
+        self.journal.record(from)?;
+        self.journal.record(to)?;
+        if let Some(parent) = to.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::rename(from, to)?;
+        remove_empty_parents(from, &self.resources);
+        Ok(())
+    }
+    pub fn write_json(&self, path: &Path, value: &Value) -> io::Result<PathBuf> {
+        let mut contents = serde_json::to_string_pretty(value)?;
+        contents.push('\n');
+        self.write(path, &contents)
+    }
+    /// Writes code or JSON that has to be pasted by hand to
+    /// `.mcdevtools/snippets/<name>`.
+    pub fn write_snippet(&self, name: &str, contents: &str) -> io::Result<PathBuf> {
+        let path = self.root.join(".mcdevtools").join("snippets").join(name);
+        self.write(&path, contents)
+    }
+    /// Adds `key` to `lang/en_us.json`, keeping the entries already there.
+    pub fn add_translation(&self, key: &str, value: &str) -> io::Result<PathBuf> {
+        let path = self.assets().join("lang").join("en_us.json");
+        let mut lang = read_json_object(&path)?;
+        lang.insert(key.to_string(), Value::from(value));
+        self.write_json(&path, &Value::Object(lang))
+    }
+    /// Adds `entry` to the `<namespace>:<tag>` tag of `registry` (`block`,
+    /// `item`...), keeping the entries already there.
+    pub fn add_to_tag(
+        &self,
+        namespace: &str,
+        registry: &str,
+        tag: &str,
+        entry: &str,
+    ) -> io::Result<PathBuf> {
+        let folder = self.version.data_folder(&format!("tags/{}", registry));
+        let path = self
+            .data_of(namespace)
+            .join(folder)
+            .join(format!("{}.json", tag));
+        let mut json = read_json_object(&path)?;
+        json.entry("replace").or_insert(Value::Bool(false));
+        let values = json
+            .entry("values")
+            .or_insert_with(|| Value::Array(Vec::new()));
+        if let Value::Array(values) = values {
+            if !values.iter().any(|value| value == entry) {
+                values.push(Value::from(entry));
+            }
+        }
+        self.write_json(&path, &Value::Object(json))
+    }
+}
+
+/// Removes the folders above `path` it left empty, stopping at `stop`.
+pub fn remove_empty_parents(path: &Path, stop: &Path) {
+    let mut dir = path.parent();
+    while let Some(parent) = dir {
+        if parent == stop || !parent.starts_with(stop) || fs::remove_dir(parent).is_err() {
+            break;
+        }
+        dir = parent.parent();
+    }
+}
+
+/// Reads a JSON object from `path`, or an empty one if the file is missing.
+pub fn read_json_object(path: &Path) -> io::Result<Map<String, Value>> {
+    if !path.exists() {
+        return Ok(Map::new());
+    }
+    match serde_json::from_str(&fs::read_to_string(path)?)? {
+        Value::Object(map) => Ok(map),
+        _ => Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("{} is not a JSON object", path.display()),
+        )),
+    }
+}
+
+/// Every file under `dir` with the given extension, sorted so results are
+/// stable between runs.
+pub fn list_files(dir: &Path, extension: &str) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        let entries = match fs::read_dir(&dir) {
+            Ok(entries) => entries,
+            Err(_) => continue,
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                pending.push(path);
+            } else if path.extension().is_some_and(|ext| ext == extension) {
+                files.push(path);
+            }
+        }
+    }
+    files.sort();
+    files
+}
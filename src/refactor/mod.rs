@@ -0,0 +1,178 @@
+pub mod namespace;
+pub mod rename;
+
+use std::{
+    collections::BTreeMap,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use serde_json::{Map, Value};
+
+use crate::project::{self, Project};
+
+/// A file a refactor moves, rewrites or both.
+pub struct Change {
+    pub from: PathBuf,
+    /// Where the file ends up, `from` when it stays in place.
+    pub to: PathBuf,
+    /// The new contents, when the file is rewritten.
+    pub contents: Option<String>,
+}
+
+impl Change {
+    /// What the change does, with paths relative to `root`.
+    pub fn describe(&self, root: &Path) -> String {
+        let relative = |path: &Path| {
+            path.strip_prefix(root)
+                .unwrap_or(path)
+                .display()
+                .to_string()
+        };
+        match (self.from == self.to, self.contents.is_some()) {
+            (true, _) => format!("Edit {}", relative(&self.from)),
+            (false, false) => format!("Move {} to {}", relative(&self.from), relative(&self.to)),
+            (false, true) => format!(
+                "Move and edit {} to {}",
+                relative(&self.from),
+                relative(&self.to)
+            ),
+        }
+    }
+}
+
+/// Rewrites the strings of `value` that `rewrite` returns a replacement
+/// for, telling it whether the string sits under a `textures` key. Returns
+/// whether anything changed.
+fn rewrite_strings(
+    value: &mut Value,
+    textures: bool,
+    rewrite: &dyn Fn(&str, bool) -> Option<String>,
+) -> bool {
+    match value {
+        Value::String(string) => match rewrite(string, textures) {
+            Some(new) => {
+                *string = new;
+                true
+            }
+            None => false,
+        },
+        Value::Array(values) => {
+            let mut changed = false;
+            for value in values {
+                changed |= rewrite_strings(value, textures, rewrite);
+            }
+            changed
+        }
+        Value::Object(map) => {
+            let mut changed = false;
+            for (key, value) in map.iter_mut() {
+                changed |= rewrite_strings(value, textures || key == "textures", rewrite);
+            }
+            changed
+        }
+        _ => false,
+    }
+}
+
+/// Renames the keys `rename` returns a new name for, keeping their order.
+fn rename_keys(map: &mut Map<String, Value>, rename: &dyn Fn(&str) -> Option<String>) -> bool {
+    if !map.keys().any(|key| rename(key).is_some()) {
+        return false;
+    }
+    *map = std::mem::take(map)
+        .into_iter()
+        .map(|(key, value)| (rename(&key).unwrap_or(key), value))
+        .collect();
+    true
+}
+
+/// Whether `path` is a lang file, whose keys are names rather than data.
+fn is_lang(path: &Path) -> bool {
+    path.parent()
+        .and_then(Path::file_name)
+        .is_some_and(|name| name == "lang")
+}
+
+/// Plans the changes of a refactor: every file in `moves` goes to its new
+/// path, and every JSON file of every namespace gets its strings rewritten
+/// by `rewrite` and, for lang files, its keys by `lang_key`. Files that
+/// can't be read or would overwrite another one stop the refactor before
+/// anything is touched.
+pub fn plan(
+    project: &Project,
+    mut moves: BTreeMap<PathBuf, PathBuf>,
+    rewrite: &dyn Fn(&str, bool) -> Option<String>,
+    lang_key: &dyn Fn(&str) -> Option<String>,
+) -> Result<Vec<Change>, String> {
+    let resources = project.resources();
+    let mut files = project::list_files(&resources.join("assets"), "json");
+    files.extend(project::list_files(&resources.join("data"), "json"));
+    let mut changes = Vec::new();
+    for path in files {
+        let read = fs::read_to_string(&path)
+            .map_err(|err| err.to_string())
+            .and_then(|text| serde_json::from_str(&text).map_err(|err| err.to_string()));
+        let mut json: Value = read.map_err(|err| {
+            let path = path.strip_prefix(project.root()).unwrap_or(&path);
+            format!(
+                "{} can't be read, so it can't be updated: {}",
+                path.display(),
+                err
+            )
+        })?;
+        let mut changed = rewrite_strings(&mut json, false, rewrite);
+        if let (true, Value::Object(map)) = (is_lang(&path), &mut json) {
+            changed |= rename_keys(map, lang_key);
+        }
+        let to = moves.remove(&path);
+        if !changed && to.is_none() {
+            continue;
+        }
+        let contents = match changed {
+            true => {
+                let mut contents =
+                    serde_json::to_string_pretty(&json).map_err(|err| err.to_string())?;
+                contents.push('\n');
+                Some(contents)
+            }
+            false => None,
+        };
+        changes.push(Change {
+            to: to.unwrap_or_else(|| path.clone()),
+            from: path,
+            contents,
+        });
+    }
+    changes.extend(moves.into_iter().map(|(from, to)| Change {
+        from,
+        to,
+        contents: None,
+    }));
+    changes.sort_by(|a, b| a.from.cmp(&b.from));
+    if let Some(change) = changes
+        .iter()
+        .find(|change| change.from != change.to && change.to.exists())
+    {
+        let path = change.to.strip_prefix(project.root()).unwrap_or(&change.to);
+        return Err(format!("{} already exists.", path.display()));
+    }
+    Ok(changes)
+}
+
+/// Carries out the changes `plan` returned, returning how many files were
+/// moved or rewritten.
+pub fn apply(project: &Project, changes: &[Change]) -> io::Result<usize> {
+    for change in changes {
+        match &change.contents {
+            Some(contents) => {
+                project.write(&change.to, contents)?;
+                if change.from != change.to {
+                    project.remove(&change.from)?;
+                }
+            }
+            None => project.rename(&change.from, &change.to)?,
+        }
+    }
+    Ok(changes.len())
+}
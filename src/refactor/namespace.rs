@@ -0,0 +1,172 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    check::Problem,
+    project::{self, Project},
+    refactor::{self, Change},
+};
+
+/// Every file under `dir`, whatever its extension.
+fn all_files(dir: &Path) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        for entry in fs::read_dir(&dir).into_iter().flatten().flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                pending.push(path);
+            } else {
+                files.push(path);
+            }
+        }
+    }
+    files
+}
+
+/// Rewrites the `from:` resource locations of commands, leaving alone the
+/// namespaces that merely end with `from`.
+fn rewrite_text(text: &str, from: &str, to: &str) -> String {
+    let pattern = format!("{}:", from);
+    let mut rewritten = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(idx) = rest.find(&pattern) {
+        rewritten.push_str(&rest[..idx]);
+        let whole = rewritten
+            .chars()
+            .next_back()
+            .is_none_or(|c| !(c.is_alphanumeric() || "_-./".contains(c)));
+        rewritten.push_str(if whole { to } else { from });
+        rewritten.push(':');
+        rest = &rest[idx + pattern.len()..];
+    }
+    rewritten.push_str(rest);
+    rewritten
+}
+
+/// Plans the move of the project's namespace to `to`: every file of
+/// `assets/<namespace>` and `data/<namespace>` moves over, every
+/// `<namespace>:` resource location in JSON and functions is rewritten, and
+/// so are the lang keys like `item.<namespace>.ruby`. Nothing changes when
+/// the namespace holds no files yet.
+pub fn plan(project: &Project, to: &str) -> Result<Vec<Change>, String> {
+    let from = project.namespace();
+    let resources = project.resources();
+    let mut moves = BTreeMap::new();
+    for kind in ["assets", "data"] {
+        let dir = resources.join(kind).join(from);
+        let target = resources.join(kind).join(to);
+        for file in all_files(&dir) {
+            if let Ok(relative) = file.strip_prefix(&dir) {
+                moves.insert(file.clone(), target.join(relative));
+            }
+        }
+    }
+    if moves.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let prefix = format!("{}:", from);
+    let rewrite = |string: &str, _| {
+        let (tag, id) = match string.strip_prefix('#') {
+            Some(id) => ("#", id),
+            None => ("", string),
+        };
+        let path = id.strip_prefix(&prefix)?;
+        Some(format!("{}{}:{}", tag, to, path))
+    };
+    let lang_key = |key: &str| {
+        let mut parts: Vec<&str> = key.split('.').collect();
+        (parts.get(1) == Some(&from)).then(|| {
+            parts[1] = to;
+            parts.join(".")
+        })
+    };
+    let mut changes = refactor::plan(project, moves, &rewrite, &lang_key)?;
+
+    // Functions of every namespace can call into this one
+    for path in project::list_files(&resources.join("data"), "mcfunction") {
+        let text = fs::read_to_string(&path).map_err(|err| err.to_string())?;
+        let rewritten = rewrite_text(&text, from, to);
+        if rewritten == text {
+            continue;
+        }
+        match changes.iter_mut().find(|change| change.from == path) {
+            Some(change) => change.contents = Some(rewritten),
+            None => changes.push(Change {
+                from: path.clone(),
+                to: path,
+                contents: Some(rewritten),
+            }),
+        }
+    }
+    changes.sort_by(|a, b| a.from.cmp(&b.from));
+    Ok(changes)
+}
+
+/// The lines of the Java and Kotlin sources under `root` that still use the
+/// `from` namespace. Code is never rewritten, so these are left to update by
+/// hand.
+pub fn code_references(root: &Path, from: &str) -> Vec<Problem> {
+    let sources = root.join("src");
+    let mut files = project::list_files(&sources, "java");
+    files.extend(project::list_files(&sources, "kt"));
+    files.sort();
+    let patterns = [format!("\"{}\"", from), format!("\"{}:", from)];
+    let mut problems = Vec::new();
+    for path in files {
+        let Ok(contents) = fs::read_to_string(&path) else {
+            continue;
+        };
+        for (idx, line) in contents.lines().enumerate() {
+            if patterns.iter().any(|pattern| line.contains(pattern)) {
+                let message = format!("Still uses '{}': {}", from, line.trim());
+                problems.push(Problem::new(&path, Some(idx + 1), message));
+            }
+        }
+    }
+    problems
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rewrites_whole_namespaces_only() {
+        assert_eq!(
+            rewrite_text("give @s ruby:gem xruby:gem", "ruby", "gems"),
+            "give @s gems:gem xruby:gem"
+        );
+        assert_eq!(
+            rewrite_text("function my_ruby:a old.ruby:b", "ruby", "gems"),
+            "function my_ruby:a old.ruby:b"
+        );
+    }
+
+    #[test]
+    fn rewrites_tags() {
+        assert_eq!(
+            rewrite_text("function #ruby:tick", "ruby", "gems"),
+            "function #gems:tick"
+        );
+    }
+
+    #[test]
+    fn rewrites_at_the_start_of_lines() {
+        assert_eq!(
+            rewrite_text("ruby:a\nruby:b", "ruby", "gems"),
+            "gems:a\ngems:b"
+        );
+    }
+
+    #[test]
+    fn leaves_other_text_unchanged() {
+        let text = "say ruby is red\nscoreboard players set @s rubies 1\n";
+        assert_eq!(rewrite_text(text, "ruby", "gems"), text);
+        assert_eq!(rewrite_text("", "ruby", "gems"), "");
+    }
+}
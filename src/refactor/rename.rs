@@ -0,0 +1,94 @@
+use std::collections::BTreeMap;
+
+use crate::{
+    check,
+    project::Project,
+    refactor::{self, Change},
+};
+
+/// The asset folders whose file named after the id belongs to it. Models
+/// are also referenced by id, as `<namespace>:block/<id>`.
+const ASSET_FOLDERS: [&str; 4] = ["blockstates", "items", "models/block", "models/item"];
+
+/// The texture folders whose file named after the id is renamed along with
+/// it, on request.
+const TEXTURE_FOLDERS: [&str; 2] = ["block", "item"];
+
+/// The data folders whose files named after the id belong to it, at any
+/// depth, like `loot_tables/blocks/<id>.json`.
+const DATA_FOLDERS: [&str; 3] = ["recipe", "loot_table", "advancement"];
+
+/// Everything needed to rename an item or block.
+pub struct RenameSpec {
+    pub from: String,
+    pub to: String,
+    /// Whether the textures named after the id are renamed too.
+    pub textures: bool,
+}
+
+/// Plans the rename of an item or block of the namespace: the files named
+/// after it are moved, and every reference to the id, its models, textures
+/// and data files is rewritten, along with its lang keys in every language.
+pub fn plan(project: &Project, rename: &RenameSpec) -> Result<Vec<Change>, String> {
+    let (from, to) = (rename.from.as_str(), rename.to.as_str());
+    let mut moves = BTreeMap::new();
+    // Resource locations of what moves, by the one they had before
+    let mut ids = BTreeMap::from([(project.resource(from), project.resource(to))]);
+    let mut textures = BTreeMap::new();
+
+    let assets = project.assets();
+    for folder in ASSET_FOLDERS {
+        let dir = assets.join(folder);
+        let path = dir.join(format!("{}.json", from));
+        if path.is_file() {
+            moves.insert(path, dir.join(format!("{}.json", to)));
+        }
+        if let Some(kind) = folder.strip_prefix("models/") {
+            ids.insert(
+                project.resource(&format!("{}/{}", kind, from)),
+                project.resource(&format!("{}/{}", kind, to)),
+            );
+        }
+    }
+    if rename.textures {
+        for folder in TEXTURE_FOLDERS {
+            let dir = assets.join("textures").join(folder);
+            for extension in ["png", "png.mcmeta"] {
+                let path = dir.join(format!("{}.{}", from, extension));
+                if path.is_file() {
+                    moves.insert(path, dir.join(format!("{}.{}", to, extension)));
+                }
+            }
+            textures.insert(
+                project.resource(&format!("{}/{}", folder, from)),
+                project.resource(&format!("{}/{}", folder, to)),
+            );
+        }
+    }
+    for folder in DATA_FOLDERS {
+        let dir = project.data_folder(folder);
+        for (path, file) in check::paths(&dir, "json") {
+            let parent = match path.strip_suffix(from) {
+                Some(parent) if parent.is_empty() || parent.ends_with('/') => parent,
+                _ => continue,
+            };
+            let new = format!("{}{}", parent, to);
+            moves.insert(file, dir.join(format!("{}.json", new)));
+            ids.insert(project.resource(&path), project.resource(&new));
+        }
+    }
+
+    let rewrite = |string: &str, texture: bool| {
+        let renamed = if texture { &textures } else { &ids };
+        renamed.get(string).cloned()
+    };
+    let namespace = project.namespace();
+    let lang_key = |key: &str| {
+        ["item", "block"].iter().find_map(|kind| {
+            let rest = key.strip_prefix(&format!("{}.{}.{}", kind, namespace, from))?;
+            (rest.is_empty() || rest.starts_with('.'))
+                .then(|| format!("{}.{}.{}{}", kind, namespace, to, rest))
+        })
+    };
+    refactor::plan(project, moves, &rewrite, &lang_key)
+}
@@ -0,0 +1,162 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use toml::{Table, Value};
+
+use crate::loader::Loader;
+
+/// The settings file kept in the project root.
+pub const FILE_NAME: &str = ".mcdevtools.toml";
+
+/// The settings kept between launches. Fields are optional so the project
+/// file only overrides what it sets over the user-level one.
+#[derive(Clone, Default, PartialEq, Debug)]
+pub struct Settings {
+    pub namespace: Option<String>,
+    pub version: Option<String>,
+    pub loader: Option<Loader>,
+    /// The folder generated files go into, relative to the project root.
+    pub output: Option<String>,
+    /// Whether new items use the handheld model by default.
+    pub handheld: Option<bool>,
+    /// Whether the generators write lang entries by default.
+    pub generate_lang: Option<bool>,
+}
+
+/// The user-level settings, the fallback of every project.
+fn user_file() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("mc-devtools").join("config.toml"))
+}
+
+impl Settings {
+    fn parse(text: &str) -> Result<Self, String> {
+        let table: Table = text
+            .parse()
+            .map_err(|err: toml::de::Error| err.to_string())?;
+        let string = |table: &Table, key: &str| table.get(key)?.as_str().map(String::from);
+        let defaults = table.get("defaults").and_then(Value::as_table);
+        let default = |key: &str| defaults?.get(key)?.as_bool();
+        let loader = match string(&table, "loader") {
+            Some(name) => match Loader::parse(&name) {
+                Some(loader) => Some(loader),
+                None => return Err(format!("'{}' is not a mod loader.", name)),
+            },
+            None => None,
+        };
+        Ok(Self {
+            namespace: string(&table, "namespace"),
+            version: string(&table, "version"),
+            loader,
+            output: string(&table, "output"),
+            handheld: default("handheld"),
+            generate_lang: default("generate_lang"),
+        })
+    }
+
+    /// Reads the settings at `path`, none being set when it doesn't exist.
+    fn read(path: &Path) -> Result<Self, String> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        fs::read_to_string(path)
+            .map_err(|err| err.to_string())
+            .and_then(|text| Self::parse(&text))
+            .map_err(|err| format!("{} can't be read: {}", path.display(), err))
+    }
+
+    /// Fills what `self` leaves unset from `fallback`.
+    pub fn or(self, fallback: Self) -> Self {
+        Self {
+            namespace: self.namespace.or(fallback.namespace),
+            version: self.version.or(fallback.version),
+            loader: self.loader.or(fallback.loader),
+            output: self.output.or(fallback.output),
+            handheld: self.handheld.or(fallback.handheld),
+            generate_lang: self.generate_lang.or(fallback.generate_lang),
+        }
+    }
+
+    /// The settings of the project at `root` and the user-level ones, with
+    /// the reason a file couldn't be read, in which case it's left out.
+    pub fn load(root: &Path) -> (Self, Self, Option<String>) {
+        let mut error = None;
+        let mut read = |path: &Path| {
+            Self::read(path).unwrap_or_else(|err| {
+                error = Some(err);
+                Self::default()
+            })
+        };
+        let user = user_file().map(|path| read(&path)).unwrap_or_default();
+        let project = read(&root.join(FILE_NAME));
+        (project, user, error)
+    }
+
+    /// What the project file has to hold for `self` to apply: the fields
+    /// `saved` already sets, and the ones that differ from what the project
+    /// would otherwise get from `inherited`.
+    pub fn over(self, saved: &Self, inherited: &Self) -> Self {
+        fn keep<T: PartialEq>(
+            value: Option<T>,
+            saved: &Option<T>,
+            inherited: &Option<T>,
+        ) -> Option<T> {
+            match saved.is_some() || value != *inherited {
+                true => value,
+                false => None,
+            }
+        }
+        Self {
+            namespace: keep(self.namespace, &saved.namespace, &inherited.namespace),
+            version: keep(self.version, &saved.version, &inherited.version),
+            loader: keep(self.loader, &saved.loader, &inherited.loader),
+            output: keep(self.output, &saved.output, &inherited.output),
+            handheld: keep(self.handheld, &saved.handheld, &inherited.handheld),
+            generate_lang: keep(
+                self.generate_lang,
+                &saved.generate_lang,
+                &inherited.generate_lang,
+            ),
+        }
+    }
+
+    fn to_toml(&self) -> Result<String, toml::ser::Error> {
+        let mut table = Table::new();
+        let strings = [
+            ("namespace", &self.namespace),
+            ("version", &self.version),
+            ("output", &self.output),
+        ];
+        for (key, value) in strings {
+            if let Some(value) = value {
+                table.insert(key.to_string(), Value::from(value.as_str()));
+            }
+        }
+        if let Some(loader) = self.loader {
+            table.insert(String::from("loader"), Value::from(loader.name()));
+        }
+        let mut defaults = Table::new();
+        let bools = [
+            ("handheld", self.handheld),
+            ("generate_lang", self.generate_lang),
+        ];
+        for (key, value) in bools {
+            if let Some(value) = value {
+                defaults.insert(key.to_string(), Value::from(value));
+            }
+        }
+        if !defaults.is_empty() {
+            table.insert(String::from("defaults"), Value::Table(defaults));
+        }
+        toml::to_string(&table)
+    }
+
+    /// Writes the settings to the settings file of the project at `root`.
+    pub fn save(&self, root: &Path) -> io::Result<()> {
+        let contents = self
+            .to_toml()
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+        fs::write(root.join(FILE_NAME), contents)
+    }
+}
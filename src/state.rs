@@ -1,23 +1,139 @@
+use crossterm::event::KeyCode;
+
 use crate::{
+    check::{unused::Unused, Problem},
+    generator::item_model::TreeRow,
+    refactor::Change,
     util::{ItemOption, StatefulList},
     AvailableOption,
 };
 
+/// The options of each screen, in the order they are listed.
+pub struct ScreenOptions<'a> {
+    pub item: Vec<ItemOption<'a>>,
+    pub block: Vec<ItemOption<'a>>,
+    pub crop: Vec<ItemOption<'a>>,
+    pub ore: Vec<ItemOption<'a>>,
+    pub feature: Vec<ItemOption<'a>>,
+    pub biome: Vec<ItemOption<'a>>,
+    pub dimension: Vec<ItemOption<'a>>,
+    pub advancement: Vec<ItemOption<'a>>,
+    pub sound: Vec<ItemOption<'a>>,
+    pub particle: Vec<ItemOption<'a>>,
+    pub pack: Vec<ItemOption<'a>>,
+    pub function: Vec<ItemOption<'a>>,
+    pub rename: Vec<ItemOption<'a>>,
+}
+
 pub struct TerminalState<'a> {
     options_state: StatefulList<AvailableOption<'a>>,
-    block_options: StatefulList<&'a str>,
+    block_options: StatefulList<ItemOption<'a>>,
+    block_text_fields: StatefulList<TextFieldState<'a>>,
     item_options: StatefulList<ItemOption<'a>>,
     item_text_fields: StatefulList<TextFieldState<'a>>,
+    item_overrides: StatefulList<String>,
+    item_model_tree: StatefulList<TreeRow>,
+    crop_options: StatefulList<ItemOption<'a>>,
+    crop_text_fields: StatefulList<TextFieldState<'a>>,
+    ore_options: StatefulList<ItemOption<'a>>,
+    ore_text_fields: StatefulList<TextFieldState<'a>>,
+    feature_options: StatefulList<ItemOption<'a>>,
+    feature_text_fields: StatefulList<TextFieldState<'a>>,
+    feature_placement: StatefulList<String>,
+    biome_options: StatefulList<ItemOption<'a>>,
+    biome_text_fields: StatefulList<TextFieldState<'a>>,
+    biome_spawns: StatefulList<String>,
+    biome_features: StatefulList<String>,
+    dimension_options: StatefulList<ItemOption<'a>>,
+    dimension_text_fields: StatefulList<TextFieldState<'a>>,
+    advancement_options: StatefulList<ItemOption<'a>>,
+    advancement_text_fields: StatefulList<TextFieldState<'a>>,
+    advancement_criteria: StatefulList<String>,
+    advancement_tree: StatefulList<TreeRow>,
+    sound_options: StatefulList<ItemOption<'a>>,
+    sound_text_fields: StatefulList<TextFieldState<'a>>,
+    sound_files: StatefulList<String>,
+    particle_options: StatefulList<ItemOption<'a>>,
+    particle_text_fields: StatefulList<TextFieldState<'a>>,
+    pack_options: StatefulList<ItemOption<'a>>,
+    pack_text_fields: StatefulList<TextFieldState<'a>>,
+    function_options: StatefulList<ItemOption<'a>>,
+    function_text_fields: StatefulList<TextFieldState<'a>>,
+    /// The problems listed by the lint and check screens, found again
+    /// whenever either opens.
+    problems: StatefulList<Problem>,
+    unused: StatefulList<Unused>,
+    rename_options: StatefulList<ItemOption<'a>>,
+    rename_text_fields: StatefulList<TextFieldState<'a>>,
+    /// The changes of the refactor being previewed.
+    changes: StatefulList<Change>,
 }
 impl<'a> TerminalState<'a> {
-    pub fn new(options: Vec<AvailableOption<'a>>, item_options: Vec<ItemOption<'a>>) -> Self {
+    pub fn new(options: Vec<AvailableOption<'a>>, screens: ScreenOptions<'a>) -> Self {
         Self {
             options_state: StatefulList::of(options),
-            item_options: StatefulList::of(item_options),
-            block_options: StatefulList::of(vec![]),
+            item_options: StatefulList::of(screens.item),
+            block_options: StatefulList::of(screens.block),
+            block_text_fields: StatefulList::of(vec![
+                TextFieldState::new("Identifier"),
+                TextFieldState::with_value("Display Name", ""),
+            ]),
             item_text_fields: StatefulList::of(vec![
                 TextFieldState::new("Identifier"),
-                TextFieldState::new("Display Name"),
+                TextFieldState::with_value("Display Name", ""),
+            ]),
+            item_overrides: StatefulList::of(vec![]),
+            item_model_tree: StatefulList::of(vec![]),
+            crop_options: StatefulList::of(screens.crop),
+            crop_text_fields: StatefulList::of(vec![TextFieldState::new("Identifier")]),
+            ore_options: StatefulList::of(screens.ore),
+            ore_text_fields: StatefulList::of(vec![TextFieldState::new("Material")]),
+            feature_options: StatefulList::of(screens.feature),
+            feature_text_fields: StatefulList::of(vec![TextFieldState::new("Identifier")]),
+            feature_placement: StatefulList::of(
+                ["count 4", "in_square", "heightmap MOTION_BLOCKING", "biome"]
+                    .map(String::from)
+                    .to_vec(),
+            ),
+            biome_options: StatefulList::of(screens.biome),
+            biome_text_fields: StatefulList::of(vec![
+                TextFieldState::new("Identifier"),
+                TextFieldState::with_value("Display Name", ""),
+            ]),
+            biome_spawns: StatefulList::of(vec![]),
+            biome_features: StatefulList::of(vec![]),
+            dimension_options: StatefulList::of(screens.dimension),
+            dimension_text_fields: StatefulList::of(vec![TextFieldState::new("Identifier")]),
+            advancement_options: StatefulList::of(screens.advancement),
+            advancement_text_fields: StatefulList::of(vec![
+                TextFieldState::new("Identifier"),
+                TextFieldState::with_value("Title", ""),
+                TextFieldState::with_value("Description", ""),
+            ]),
+            function_options: StatefulList::of(screens.function),
+            function_text_fields: StatefulList::of(vec![TextFieldState::new("Path")]),
+            problems: StatefulList::of(vec![]),
+            unused: StatefulList::of(vec![]),
+            rename_options: StatefulList::of(screens.rename),
+            rename_text_fields: StatefulList::of(vec![
+                TextFieldState::new("Identifier"),
+                TextFieldState::new("New Identifier"),
+            ]),
+            changes: StatefulList::of(vec![]),
+            advancement_criteria: StatefulList::of(vec![]),
+            advancement_tree: StatefulList::of(vec![]),
+            sound_options: StatefulList::of(screens.sound),
+            sound_text_fields: StatefulList::of(vec![
+                TextFieldState::new("Identifier"),
+                TextFieldState::with_value("Subtitle", ""),
+            ]),
+            sound_files: StatefulList::of(vec![]),
+            particle_options: StatefulList::of(screens.particle),
+            particle_text_fields: StatefulList::of(vec![TextFieldState::new("Identifier")]),
+            pack_options: StatefulList::of(screens.pack),
+            pack_text_fields: StatefulList::of(vec![
+                TextFieldState::with_value("Folder", ""),
+                TextFieldState::with_value("Description", ""),
             ]),
         }
     }
@@ -26,28 +142,202 @@ impl<'a> TerminalState<'a> {
         &mut self.options_state
     }
 
-    pub fn block_options(&mut self) -> &mut StatefulList<&'a str> {
+    /// The options of every screen, for the defaults they share.
+    pub fn all_options(&mut self) -> Vec<&mut StatefulList<ItemOption<'a>>> {
+        vec![
+            &mut self.item_options,
+            &mut self.block_options,
+            &mut self.crop_options,
+            &mut self.ore_options,
+            &mut self.feature_options,
+            &mut self.biome_options,
+            &mut self.dimension_options,
+            &mut self.advancement_options,
+            &mut self.sound_options,
+            &mut self.particle_options,
+            &mut self.pack_options,
+            &mut self.function_options,
+            &mut self.rename_options,
+        ]
+    }
+
+    pub fn block_options(&mut self) -> &mut StatefulList<ItemOption<'a>> {
         &mut self.block_options
     }
+    pub fn block_text_fields(&mut self) -> &mut StatefulList<TextFieldState<'a>> {
+        &mut self.block_text_fields
+    }
 
     pub fn item_text_fields(&mut self) -> &mut StatefulList<TextFieldState<'a>> {
         &mut self.item_text_fields
     }
+    pub fn item_overrides(&mut self) -> &mut StatefulList<String> {
+        &mut self.item_overrides
+    }
+    pub fn item_model_tree(&mut self) -> &mut StatefulList<TreeRow> {
+        &mut self.item_model_tree
+    }
     pub fn item_options(&mut self) -> &mut StatefulList<ItemOption<'a>> {
         &mut self.item_options
     }
+
+    pub fn crop_options(&mut self) -> &mut StatefulList<ItemOption<'a>> {
+        &mut self.crop_options
+    }
+    pub fn crop_text_fields(&mut self) -> &mut StatefulList<TextFieldState<'a>> {
+        &mut self.crop_text_fields
+    }
+
+    pub fn ore_options(&mut self) -> &mut StatefulList<ItemOption<'a>> {
+        &mut self.ore_options
+    }
+    pub fn ore_text_fields(&mut self) -> &mut StatefulList<TextFieldState<'a>> {
+        &mut self.ore_text_fields
+    }
+
+    pub fn feature_options(&mut self) -> &mut StatefulList<ItemOption<'a>> {
+        &mut self.feature_options
+    }
+    pub fn feature_text_fields(&mut self) -> &mut StatefulList<TextFieldState<'a>> {
+        &mut self.feature_text_fields
+    }
+    pub fn feature_placement(&mut self) -> &mut StatefulList<String> {
+        &mut self.feature_placement
+    }
+
+    pub fn biome_options(&mut self) -> &mut StatefulList<ItemOption<'a>> {
+        &mut self.biome_options
+    }
+    pub fn biome_text_fields(&mut self) -> &mut StatefulList<TextFieldState<'a>> {
+        &mut self.biome_text_fields
+    }
+    pub fn biome_spawns(&mut self) -> &mut StatefulList<String> {
+        &mut self.biome_spawns
+    }
+    pub fn biome_features(&mut self) -> &mut StatefulList<String> {
+        &mut self.biome_features
+    }
+
+    pub fn dimension_options(&mut self) -> &mut StatefulList<ItemOption<'a>> {
+        &mut self.dimension_options
+    }
+    pub fn dimension_text_fields(&mut self) -> &mut StatefulList<TextFieldState<'a>> {
+        &mut self.dimension_text_fields
+    }
+
+    pub fn advancement_options(&mut self) -> &mut StatefulList<ItemOption<'a>> {
+        &mut self.advancement_options
+    }
+    pub fn advancement_text_fields(&mut self) -> &mut StatefulList<TextFieldState<'a>> {
+        &mut self.advancement_text_fields
+    }
+    pub fn advancement_criteria(&mut self) -> &mut StatefulList<String> {
+        &mut self.advancement_criteria
+    }
+    pub fn advancement_tree(&mut self) -> &mut StatefulList<TreeRow> {
+        &mut self.advancement_tree
+    }
+
+    pub fn sound_options(&mut self) -> &mut StatefulList<ItemOption<'a>> {
+        &mut self.sound_options
+    }
+    pub fn sound_text_fields(&mut self) -> &mut StatefulList<TextFieldState<'a>> {
+        &mut self.sound_text_fields
+    }
+    pub fn sound_files(&mut self) -> &mut StatefulList<String> {
+        &mut self.sound_files
+    }
+
+    pub fn particle_options(&mut self) -> &mut StatefulList<ItemOption<'a>> {
+        &mut self.particle_options
+    }
+    pub fn particle_text_fields(&mut self) -> &mut StatefulList<TextFieldState<'a>> {
+        &mut self.particle_text_fields
+    }
+
+    pub fn pack_options(&mut self) -> &mut StatefulList<ItemOption<'a>> {
+        &mut self.pack_options
+    }
+    pub fn pack_text_fields(&mut self) -> &mut StatefulList<TextFieldState<'a>> {
+        &mut self.pack_text_fields
+    }
+
+    pub fn function_options(&mut self) -> &mut StatefulList<ItemOption<'a>> {
+        &mut self.function_options
+    }
+    pub fn function_text_fields(&mut self) -> &mut StatefulList<TextFieldState<'a>> {
+        &mut self.function_text_fields
+    }
+
+    pub fn problems(&mut self) -> &mut StatefulList<Problem> {
+        &mut self.problems
+    }
+    pub fn unused(&mut self) -> &mut StatefulList<Unused> {
+        &mut self.unused
+    }
+
+    pub fn rename_options(&mut self) -> &mut StatefulList<ItemOption<'a>> {
+        &mut self.rename_options
+    }
+    pub fn rename_text_fields(&mut self) -> &mut StatefulList<TextFieldState<'a>> {
+        &mut self.rename_text_fields
+    }
+    pub fn changes(&mut self) -> &mut StatefulList<Change> {
+        &mut self.changes
+    }
+}
+
+impl StatefulList<Unused> {
+    /// Space picks the selected entry for deletion and `a` picks all of
+    /// them, or none when they all are.
+    pub fn handle_key(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Char(' ') => {
+                if let Some(entry) = self.selected_mut() {
+                    entry.selected = !entry.selected;
+                }
+            }
+            KeyCode::Char('a') => {
+                let select = self.elements().iter().any(|entry| !entry.selected);
+                for entry in self.elements_mut() {
+                    entry.selected = select;
+                }
+            }
+            KeyCode::Down => self.next(),
+            KeyCode::Up => self.previous(),
+            _ => (),
+        }
+    }
+
+    /// The entries picked for deletion.
+    pub fn picked(&self) -> Vec<&Unused> {
+        self.elements()
+            .iter()
+            .filter(|entry| entry.selected)
+            .collect()
+    }
 }
 
 pub struct TextFieldState<'a> {
     title: &'a str,
     data: String,
+    lowercase: bool,
 }
 
 impl<'a> TextFieldState<'a> {
+    /// A field for identifiers, which are always lowercase.
     pub fn new(title: &'a str) -> Self {
         Self {
-            title: title,
+            title,
             data: String::from("example"),
+            lowercase: true,
+        }
+    }
+    pub fn with_value(title: &'a str, value: &str) -> Self {
+        Self {
+            title,
+            data: String::from(value),
+            lowercase: false,
         }
     }
     pub fn title(&self) -> &'a str {
@@ -56,4 +346,43 @@ impl<'a> TextFieldState<'a> {
     pub fn data(&mut self) -> &mut String {
         &mut self.data
     }
+    pub fn value(&self) -> &str {
+        &self.data
+    }
+    pub fn push(&mut self, c: char) {
+        if self.lowercase {
+            self.data.extend(c.to_lowercase());
+        } else {
+            self.data.push(c);
+        }
+    }
+}
+
+impl<'a> StatefulList<TextFieldState<'a>> {
+    /// Edits the selected field, Tab and Shift+Tab moving between fields.
+    pub fn handle_key(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Tab => self.next(),
+            KeyCode::BackTab => self.previous(),
+            KeyCode::Char(c) => {
+                if let Some(field) = self.selected_mut() {
+                    field.push(c);
+                }
+            }
+            KeyCode::Backspace => {
+                if let Some(field) = self.selected_mut() {
+                    field.data().pop();
+                }
+            }
+            _ => (),
+        }
+    }
+
+    /// The trimmed contents of the field called `title`.
+    pub fn value(&self, title: &str) -> &str {
+        self.elements()
+            .iter()
+            .find(|field| field.title() == title)
+            .map_or("", |field| field.value().trim())
+    }
 }
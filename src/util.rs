@@ -1,3 +1,4 @@
+use crossterm::event::KeyCode;
 use tui::{
     style::{Color, Modifier, Style},
     text::{Span, Spans},
@@ -15,17 +16,12 @@ pub fn create_menu<'a>(title: &'a str, entries: Vec<ListItem<'a>>, active: bool)
             )
             .highlight_symbol(" > ");
     }
-    return menu;
+    menu
 }
 
-pub fn text_field<'a>(
-    current_mode: &EditMode,
-    active_mode: EditMode,
-    raw_paragraph: Paragraph<'a>,
-    title: &'a str,
-) -> Paragraph<'a> {
+pub fn text_field<'a>(raw_paragraph: Paragraph<'a>, title: &'a str, active: bool) -> Paragraph<'a> {
     let mut style = Style::default();
-    if *current_mode == active_mode {
+    if active {
         style = style.fg(Color::Rgb(255, 153, 0));
     }
     raw_paragraph
@@ -58,6 +54,45 @@ pub fn menu_spans<'a>() -> Spans<'a> {
     ];
     Spans::from(line)
 }
+pub fn option_editing_spans<'a>() -> Spans<'a> {
+    let bold = bold();
+    let line = vec![
+        Span::raw("Press "),
+        Span::styled("Space ", bold),
+        Span::raw("to toggle, "),
+        Span::styled("left ", bold),
+        Span::raw("or "),
+        Span::styled("right ", bold),
+        Span::raw("to change a value, or type to edit it."),
+    ];
+    Spans::from(line)
+}
+pub fn text_field_spans<'a>() -> Spans<'a> {
+    let line = vec![
+        Span::raw("Press "),
+        Span::styled("Tab ", bold()),
+        Span::raw("to move to the next field."),
+    ];
+    Spans::from(line)
+}
+/// The help line of the row editors, see `StatefulList<String>::handle_key`.
+pub fn row_spans<'a>() -> Spans<'a> {
+    Spans::from(vec![
+        Span::raw("Press "),
+        Span::styled("Enter ", bold()),
+        Span::raw("to add a row, "),
+        Span::styled("Backspace ", bold()),
+        Span::raw("on an empty row removes it."),
+    ])
+}
+/// A `Press <key> <action>` help line.
+pub fn key_spans<'a>(key: &'a str, action: &'a str) -> Spans<'a> {
+    Spans::from(vec![
+        Span::raw("Press "),
+        Span::styled(format!("{} ", key), bold()),
+        Span::raw(action),
+    ])
+}
 pub fn stop_editing_spans<'a>() -> Spans<'a> {
     let line = vec![
         Span::raw("Press "),
@@ -69,18 +104,68 @@ pub fn stop_editing_spans<'a>() -> Spans<'a> {
 #[derive(PartialEq, Eq, Debug)]
 pub enum EditMode {
     Namespace,
+    Version,
     MainMenu,
     ItemMenu,
     None,
-    ItemIdentifier,
-    ItemDisplayName,
+    ItemTextField,
+    ItemOverrides,
+    ItemModelTree,
+    BlockMenu,
+    BlockTextField,
+    CropMenu,
+    CropTextField,
+    OreMenu,
+    OreTextField,
+    FeatureMenu,
+    FeatureTextField,
+    FeaturePlacement,
+    BiomeMenu,
+    BiomeTextField,
+    BiomeSpawns,
+    BiomeFeatures,
+    DimensionMenu,
+    DimensionTextField,
+    AdvancementMenu,
+    AdvancementTextField,
+    AdvancementCriteria,
+    AdvancementTree,
+    SoundMenu,
+    SoundTextField,
+    SoundFiles,
+    ParticleMenu,
+    ParticleTextField,
+    PackMenu,
+    PackTextField,
+    FunctionMenu,
+    FunctionTextField,
+    ConfirmDelete,
+    RenameMenu,
+    RenameTextField,
+    ChangePreview,
 }
 
 #[derive(PartialEq, Eq)]
+#[allow(clippy::enum_variant_names)]
 pub enum MenuType {
     MainMenu,
     ItemMenu,
     BlockMenu,
+    CropMenu,
+    OreMenu,
+    FeatureMenu,
+    BiomeMenu,
+    DimensionMenu,
+    AdvancementMenu,
+    SoundMenu,
+    ParticleMenu,
+    PackMenu,
+    FunctionMenu,
+    LintMenu,
+    IntegrityMenu,
+    UnusedMenu,
+    RenameMenu,
+    MigrationMenu,
 }
 /// The value represents how far the menu is
 /// From the first screen.
@@ -90,6 +175,21 @@ impl MenuType {
             Self::MainMenu => 0,
             Self::ItemMenu => 1,
             Self::BlockMenu => 1,
+            Self::CropMenu => 1,
+            Self::OreMenu => 1,
+            Self::FeatureMenu => 1,
+            Self::BiomeMenu => 1,
+            Self::DimensionMenu => 1,
+            Self::AdvancementMenu => 1,
+            Self::SoundMenu => 1,
+            Self::ParticleMenu => 1,
+            Self::PackMenu => 1,
+            Self::FunctionMenu => 1,
+            Self::LintMenu => 1,
+            Self::IntegrityMenu => 1,
+            Self::UnusedMenu => 1,
+            Self::RenameMenu => 1,
+            Self::MigrationMenu => 1,
         }
     }
     pub fn can_navigate_back(&self) -> bool {
@@ -115,7 +215,7 @@ impl<T> StatefulList<T> {
     pub fn of(list: Vec<T>) -> Self {
         Self {
             state: ListState::default(),
-            list: list,
+            list,
         }
     }
 
@@ -131,19 +231,45 @@ impl<T> StatefulList<T> {
     pub fn elements(&self) -> &Vec<T> {
         &self.list
     }
-    pub fn update(&mut self, idx: usize, item: T) {
-        match self.list.get(idx) {
-            Some(_) => self.list.insert(idx, item),
-            None => (),
-        }
-    }
-    // pub fn get(&self, idx: usize) -> &mut T {
-    //     self.list.get_mut(idx).unwrap()
-    // }
     pub fn elements_mut(&mut self) -> &mut Vec<T> {
         &mut self.list
     }
+    pub fn selected_mut(&mut self) -> Option<&mut T> {
+        match self.state.selected() {
+            Some(idx) => self.list.get_mut(idx),
+            None => None,
+        }
+    }
+    /// Adds `item` after the selected element and selects it.
+    pub fn insert_after_selected(&mut self, item: T) {
+        let idx = match self.state.selected() {
+            Some(idx) => idx + 1,
+            None => self.list.len(),
+        };
+        self.list.insert(idx, item);
+        self.state.select(Some(idx));
+    }
+    /// Replaces the elements after the first `keep` ones with `items`.
+    pub fn replace_after(&mut self, keep: usize, items: Vec<T>) {
+        self.list.truncate(keep);
+        self.list.extend(items);
+    }
+    /// Removes the selected element and selects the one before it.
+    pub fn remove_selected(&mut self) {
+        if let Some(idx) = self.state.selected() {
+            if idx < self.list.len() {
+                self.list.remove(idx);
+            }
+            self.state.select(match self.list.len() {
+                0 => None,
+                len => Some(idx.saturating_sub(1).min(len - 1)),
+            });
+        }
+    }
     pub fn next(&mut self) {
+        if self.list.is_empty() {
+            return;
+        }
         let i = match self.state.selected() {
             Some(i) => {
                 if i >= self.list.len() - 1 {
@@ -157,6 +283,9 @@ impl<T> StatefulList<T> {
         self.state.select(Some(i));
     }
     pub fn previous(&mut self) {
+        if self.list.is_empty() {
+            return;
+        }
         let i = match self.state.selected() {
             Some(i) => {
                 if i == 0 {
@@ -170,16 +299,35 @@ impl<T> StatefulList<T> {
         self.state.select(Some(i));
     }
 }
+impl StatefulList<String> {
+    /// Edits free-form rows: Enter adds a row below the selected one and
+    /// Backspace on an empty row removes it.
+    pub fn handle_key(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Enter => self.insert_after_selected(String::new()),
+            KeyCode::Down => self.next(),
+            KeyCode::Up => self.previous(),
+            KeyCode::Char(c) => match self.selected_mut() {
+                Some(row) => row.push(c),
+                None => self.insert_after_selected(c.to_string()),
+            },
+            KeyCode::Backspace => match self.selected_mut() {
+                Some(row) if !row.is_empty() => {
+                    row.pop();
+                }
+                _ => self.remove_selected(),
+            },
+            _ => (),
+        }
+    }
+}
 pub struct AvailableOption<'a> {
     option: &'a str,
     desc: &'a str,
 }
 impl<'a> AvailableOption<'a> {
     pub fn new(option: &'a str, desc: &'a str) -> AvailableOption<'a> {
-        Self {
-            option: option,
-            desc: desc,
-        }
+        Self { option, desc }
     }
     pub fn get_option(&self) -> &'a str {
         self.option
@@ -189,24 +337,99 @@ impl<'a> AvailableOption<'a> {
     }
 }
 
+/// The value an option holds, which decides how it is edited in the list.
+pub enum OptionValue {
+    Toggle(bool),
+    /// Numbers keep the text being typed so half-written values can be
+    /// shown, they are only checked against the range when read.
+    Int {
+        input: String,
+        min: i64,
+        max: i64,
+    },
+    Float {
+        input: String,
+        min: f64,
+        max: f64,
+    },
+    Choice {
+        choices: Vec<String>,
+        selected: usize,
+    },
+    Text(String),
+    /// A hex color such as `78a7ff`, shown next to a swatch. Empty when
+    /// left to the default.
+    Color(String),
+}
+
+/// How much Left and Right change a float option.
+const FLOAT_STEP: f64 = 0.1;
+
 pub struct ItemOption<'a> {
     option: &'a str,
     desc: &'a str,
-    active: bool,
+    value: OptionValue,
 }
 impl<'a> ItemOption<'a> {
     pub fn new(option: &'a str, desc: &'a str) -> Self {
         Self {
             option,
             desc,
-            active: false,
+            value: OptionValue::Toggle(false),
         }
     }
     pub fn active(option: &'a str, desc: &'a str) -> Self {
         Self {
-            option: option,
-            desc: desc,
-            active: true,
+            option,
+            desc,
+            value: OptionValue::Toggle(true),
+        }
+    }
+    pub fn int(option: &'a str, desc: &'a str, value: i64, min: i64, max: i64) -> Self {
+        Self {
+            option,
+            desc,
+            value: OptionValue::Int {
+                input: value.to_string(),
+                min,
+                max,
+            },
+        }
+    }
+    pub fn float(option: &'a str, desc: &'a str, value: f64, min: f64, max: f64) -> Self {
+        Self {
+            option,
+            desc,
+            value: OptionValue::Float {
+                input: value.to_string(),
+                min,
+                max,
+            },
+        }
+    }
+    /// An option that picks one of `choices`, starting with the first one.
+    pub fn choice(option: &'a str, desc: &'a str, choices: Vec<&str>) -> Self {
+        Self {
+            option,
+            desc,
+            value: OptionValue::Choice {
+                choices: choices.into_iter().map(String::from).collect(),
+                selected: 0,
+            },
+        }
+    }
+    pub fn text(option: &'a str, desc: &'a str, value: &str) -> Self {
+        Self {
+            option,
+            desc,
+            value: OptionValue::Text(String::from(value)),
+        }
+    }
+    pub fn color(option: &'a str, desc: &'a str, value: &str) -> Self {
+        Self {
+            option,
+            desc,
+            value: OptionValue::Color(String::from(value)),
         }
     }
     pub fn get_option(&self) -> &'a str {
@@ -216,9 +439,286 @@ impl<'a> ItemOption<'a> {
         self.desc
     }
     pub fn is_active(&self) -> bool {
-        self.active
+        matches!(self.value, OptionValue::Toggle(true))
+    }
+    /// The value as shown between the brackets of the option list.
+    pub fn display(&self) -> String {
+        match &self.value {
+            OptionValue::Toggle(active) => String::from(if *active { "x" } else { " " }),
+            OptionValue::Int { input, .. } | OptionValue::Float { input, .. } => input.clone(),
+            OptionValue::Choice { choices, selected } => format!("< {} >", choices[*selected]),
+            OptionValue::Text(text) | OptionValue::Color(text) => text.clone(),
+        }
+    }
+    /// The color of a valid color option, for its swatch.
+    fn swatch(&self) -> Option<Color> {
+        match &self.value {
+            OptionValue::Color(hex) if hex.len() == 6 => {
+                let rgb = u32::from_str_radix(hex, 16).ok()?;
+                Some(Color::Rgb((rgb >> 16) as u8, (rgb >> 8) as u8, rgb as u8))
+            }
+            _ => None,
+        }
+    }
+    /// Moves a number by one step or a choice to its neighbour, `forward`
+    /// being Right in the option list.
+    pub fn step(&mut self, forward: bool) {
+        match &mut self.value {
+            OptionValue::Int { input, min, max } => {
+                let value = input.parse::<i64>().unwrap_or(*min);
+                let value = if forward { value + 1 } else { value - 1 };
+                *input = value.clamp(*min, *max).to_string();
+            }
+            OptionValue::Float { input, min, max } => {
+                let value = input.parse::<f64>().unwrap_or(*min);
+                let value = if forward {
+                    value + FLOAT_STEP
+                } else {
+                    value - FLOAT_STEP
+                };
+                // Round away the error adding the step leaves behind
+                let value = (value.clamp(*min, *max) * 1000.0).round() / 1000.0;
+                *input = value.to_string();
+            }
+            OptionValue::Choice { choices, selected } => {
+                *selected = if forward {
+                    (*selected + 1) % choices.len()
+                } else {
+                    (*selected + choices.len() - 1) % choices.len()
+                };
+            }
+            _ => (),
+        }
+    }
+    /// Types `c` into a number or text option. Space toggles on/off options.
+    pub fn push(&mut self, c: char) {
+        match &mut self.value {
+            OptionValue::Toggle(active) if c == ' ' => *active = !*active,
+            OptionValue::Int { input, .. } if c.is_ascii_digit() || c == '-' => input.push(c),
+            OptionValue::Float { input, .. } if c.is_ascii_digit() || c == '-' || c == '.' => {
+                input.push(c)
+            }
+            OptionValue::Text(text) => text.push(c),
+            OptionValue::Color(hex) if c.is_ascii_hexdigit() && hex.len() < 6 => {
+                hex.extend(c.to_lowercase())
+            }
+            _ => (),
+        }
+    }
+    pub fn pop(&mut self) {
+        match &mut self.value {
+            OptionValue::Int { input, .. }
+            | OptionValue::Float { input, .. }
+            | OptionValue::Text(input)
+            | OptionValue::Color(input) => {
+                input.pop();
+            }
+            _ => (),
+        }
+    }
+    /// Checks that numbers parse and fall in their range.
+    pub fn validate(&self) -> Result<(), String> {
+        let out_of_range = |min: &dyn std::fmt::Display, max: &dyn std::fmt::Display| {
+            format!("{} must be between {} and {}.", self.option, min, max)
+        };
+        match &self.value {
+            OptionValue::Int { input, min, max } => match input.parse::<i64>() {
+                Ok(value) if (*min..=*max).contains(&value) => Ok(()),
+                _ => Err(out_of_range(min, max)),
+            },
+            OptionValue::Float { input, min, max } => match input.parse::<f64>() {
+                Ok(value) if (*min..=*max).contains(&value) => Ok(()),
+                _ => Err(out_of_range(min, max)),
+            },
+            OptionValue::Color(hex) if !hex.is_empty() && self.swatch().is_none() => Err(format!(
+                "{} must be a 6 digit hex color such as 78a7ff.",
+                self.option
+            )),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl<'a> StatefulList<ItemOption<'a>> {
+    /// Edits the selected option as described by `option_editing_spans`.
+    pub fn handle_key(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Char(c) => {
+                if let Some(option) = self.selected_mut() {
+                    option.push(c);
+                }
+            }
+            KeyCode::Backspace => {
+                if let Some(option) = self.selected_mut() {
+                    option.pop();
+                }
+            }
+            KeyCode::Left | KeyCode::Right => {
+                if let Some(option) = self.selected_mut() {
+                    option.step(code == KeyCode::Right);
+                }
+            }
+            KeyCode::Down => self.next(),
+            KeyCode::Up => self.previous(),
+            _ => (),
+        }
+    }
+    /// One line per option, showing invalid values in red until they are
+    /// fixed and colors next to a swatch.
+    pub fn list_items(&self) -> Vec<ListItem<'static>> {
+        self.list
+            .iter()
+            .map(|element| {
+                let style = match element.validate() {
+                    Ok(()) => Style::default(),
+                    Err(_) => Style::default().fg(Color::Red),
+                };
+                let mut spans = vec![Span::raw("[")];
+                if let Some(color) = element.swatch() {
+                    spans.push(Span::styled(
+                        "\u{2588}\u{2588} ",
+                        Style::default().fg(color),
+                    ));
+                }
+                spans.push(Span::raw(format!(
+                    "{}] {}:  {}",
+                    element.display(),
+                    element.get_option(),
+                    element.get_desc()
+                )));
+                ListItem::new(Spans::from(spans)).style(style)
+            })
+            .collect()
+    }
+    fn find(&self, name: &str) -> Option<&ItemOption<'a>> {
+        self.list.iter().find(|option| option.get_option() == name)
+    }
+    /// Whether the option called `name` is turned on.
+    pub fn is_active(&self, name: &str) -> bool {
+        self.find(name).is_some_and(|option| option.is_active())
+    }
+    /// The validated value of the int option called `name`.
+    pub fn int(&self, name: &str) -> Result<i64, String> {
+        match self.find(name) {
+            Some(option) => match &option.value {
+                OptionValue::Int { input, .. } => {
+                    option.validate()?;
+                    Ok(input.parse().unwrap_or_default())
+                }
+                _ => Err(format!("{} is not a whole number option.", name)),
+            },
+            None => Err(format!("There is no option called {}.", name)),
+        }
+    }
+    /// The validated value of the float option called `name`.
+    pub fn float(&self, name: &str) -> Result<f64, String> {
+        match self.find(name) {
+            Some(option) => match &option.value {
+                OptionValue::Float { input, .. } => {
+                    option.validate()?;
+                    Ok(input.parse().unwrap_or_default())
+                }
+                _ => Err(format!("{} is not a number option.", name)),
+            },
+            None => Err(format!("There is no option called {}.", name)),
+        }
+    }
+    /// The selected entry of the choice option called `name`.
+    pub fn choice(&self, name: &str) -> &str {
+        match self.find(name).map(|option| &option.value) {
+            Some(OptionValue::Choice { choices, selected }) => &choices[*selected],
+            _ => "",
+        }
     }
-    pub fn toggle(&mut self) {
-        self.active = !self.active
+    /// Replaces the entries of the choice option called `name`, keeping the
+    /// selected one if it is still there.
+    pub fn set_choices(&mut self, name: &str, entries: Vec<String>) {
+        let option = self.list.iter_mut().find(|option| option.option == name);
+        if let Some(ItemOption {
+            value: OptionValue::Choice { choices, selected },
+            ..
+        }) = option
+        {
+            if entries.is_empty() {
+                return;
+            }
+            let current = &choices[*selected];
+            *selected = entries
+                .iter()
+                .position(|entry| entry == current)
+                .unwrap_or(0);
+            *choices = entries;
+        }
+    }
+    /// The value of the option called `name` as text, `true`/`false` for
+    /// toggles, after checking numbers are in range.
+    pub fn raw(&self, name: &str) -> Result<String, String> {
+        let option = self
+            .find(name)
+            .ok_or_else(|| format!("There is no option called {}.", name))?;
+        option.validate()?;
+        Ok(match &option.value {
+            OptionValue::Toggle(active) => active.to_string(),
+            OptionValue::Int { input, .. }
+            | OptionValue::Float { input, .. }
+            | OptionValue::Text(input)
+            | OptionValue::Color(input) => input.clone(),
+            OptionValue::Choice { choices, selected } => choices[*selected].clone(),
+        })
+    }
+    /// The validated value of the color option called `name`, or `None`
+    /// when it was left empty.
+    pub fn color(&self, name: &str) -> Result<Option<i64>, String> {
+        match self.find(name) {
+            Some(option) => match &option.value {
+                OptionValue::Color(hex) if hex.is_empty() => Ok(None),
+                OptionValue::Color(hex) => {
+                    option.validate()?;
+                    Ok(i64::from_str_radix(hex, 16).ok())
+                }
+                _ => Err(format!("{} is not a color option.", name)),
+            },
+            None => Err(format!("There is no option called {}.", name)),
+        }
+    }
+    /// The state of the on/off option called `name`, `None` when there is
+    /// no such option.
+    pub fn toggle(&self, name: &str) -> Option<bool> {
+        match self.find(name)?.value {
+            OptionValue::Toggle(active) => Some(active),
+            _ => None,
+        }
+    }
+    /// Turns the on/off option called `name` on or off.
+    pub fn set_active(&mut self, name: &str, active: bool) {
+        let option = self.list.iter_mut().find(|option| option.option == name);
+        if let Some(ItemOption {
+            value: OptionValue::Toggle(value),
+            ..
+        }) = option
+        {
+            *value = active;
+        }
+    }
+    /// Selects `value` in the choice option called `name` if it is one of
+    /// its entries.
+    pub fn set_choice(&mut self, name: &str, value: &str) {
+        let option = self.list.iter_mut().find(|option| option.option == name);
+        if let Some(ItemOption {
+            value: OptionValue::Choice { choices, selected },
+            ..
+        }) = option
+        {
+            if let Some(idx) = choices.iter().position(|choice| choice == value) {
+                *selected = idx;
+            }
+        }
+    }
+    /// The trimmed contents of the text option called `name`.
+    pub fn text(&self, name: &str) -> &str {
+        match self.find(name).map(|option| &option.value) {
+            Some(OptionValue::Text(text)) => text.trim(),
+            _ => "",
+        }
     }
 }
@@ -0,0 +1,119 @@
+use std::fmt;
+
+/// Data pack folders that lost their plural `s` in 1.21.
+const RENAMED_FOLDERS: [&str; 13] = [
+    "advancement",
+    "function",
+    "item_modifier",
+    "loot_table",
+    "predicate",
+    "recipe",
+    "structure",
+    "tags/block",
+    "tags/entity_type",
+    "tags/fluid",
+    "tags/function",
+    "tags/game_event",
+    "tags/item",
+];
+
+/// A Minecraft release such as `1.20.1`.
+///
+/// Generators use it to decide which file layout and JSON format to emit,
+/// since both changed a lot between releases.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub struct GameVersion {
+    minor: u32,
+    patch: u32,
+}
+
+impl GameVersion {
+    pub const fn new(minor: u32, patch: u32) -> Self {
+        Self { minor, patch }
+    }
+    /// Parses versions written as `1.20` or `1.20.5`.
+    pub fn parse(input: &str) -> Option<Self> {
+        let mut parts = input.trim().split('.');
+        if parts.next()? != "1" {
+            return None;
+        }
+        let minor = parts.next()?.parse().ok()?;
+        let patch = match parts.next() {
+            Some(patch) => patch.parse().ok()?,
+            None => 0,
+        };
+        if parts.next().is_some() {
+            return None;
+        }
+        Some(Self { minor, patch })
+    }
+    pub fn at_least(&self, minor: u32, patch: u32) -> bool {
+        *self >= Self::new(minor, patch)
+    }
+    /// The data pack folder for `kind`, such as `loot_table` or `tags/item`,
+    /// in the plural form used before 1.21 when needed.
+    pub fn data_folder(&self, kind: &str) -> String {
+        if !self.at_least(21, 0) && RENAMED_FOLDERS.contains(&kind) {
+            format!("{}s", kind)
+        } else {
+            kind.to_string()
+        }
+    }
+}
+
+impl fmt::Display for GameVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.patch == 0 {
+            write!(f, "1.{}", self.minor)
+        } else {
+            write!(f, "1.{}.{}", self.minor, self.patch)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_versions() {
+        assert_eq!(GameVersion::parse("1.20"), Some(GameVersion::new(20, 0)));
+        assert_eq!(GameVersion::parse("1.20.5"), Some(GameVersion::new(20, 5)));
+        assert_eq!(
+            GameVersion::parse(" 1.21.4 "),
+            Some(GameVersion::new(21, 4))
+        );
+        for invalid in ["", "1", "1.", "2.0", "1.20.5.1", "1.x", "1.20.a", "20.1"] {
+            assert_eq!(GameVersion::parse(invalid), None, "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn at_least_compares_minor_then_patch() {
+        let version = GameVersion::new(20, 5);
+        assert!(version.at_least(20, 5));
+        assert!(version.at_least(20, 4));
+        assert!(version.at_least(19, 9));
+        assert!(!version.at_least(20, 6));
+        assert!(!version.at_least(21, 0));
+    }
+
+    #[test]
+    fn display_leaves_out_patch_zero() {
+        assert_eq!(GameVersion::new(21, 0).to_string(), "1.21");
+        assert_eq!(GameVersion::new(20, 1).to_string(), "1.20.1");
+    }
+
+    #[test]
+    fn data_folders_lose_their_plural_in_1_21() {
+        assert_eq!(
+            GameVersion::new(20, 6).data_folder("loot_table"),
+            "loot_tables"
+        );
+        assert_eq!(
+            GameVersion::new(21, 0).data_folder("loot_table"),
+            "loot_table"
+        );
+        assert_eq!(GameVersion::new(20, 1).data_folder("worldgen"), "worldgen");
+    }
+}